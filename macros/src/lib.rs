@@ -0,0 +1,254 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Delimiter, TokenStream as TokenStream2, TokenTree};
+use quote::quote;
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::SystemTime;
+use syn::LitStr;
+
+/// A copy of Vapor's `locale/` shipped with this crate, so keys can be checked without Vapor's sources.
+/// Vapor's tests keep the two in sync.
+const VAPOR_LOCALES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/locale");
+
+/// Directories of the crate being compiled that are searched for locale files.
+const CRATE_LOCALES: [&str; 2] = ["locales", "locale"];
+
+/// Extensions rust-i18n reads locale files from.
+const EXTENSIONS: [&str; 4] = ["yml", "yaml", "json", "toml"];
+
+const MAX_SUGGESTIONS: usize = 3;
+
+/// The translations of one crate, as `rust_i18n::i18n!` reads them.
+#[derive(Default)]
+struct Source {
+    files: Vec<PathBuf>,
+    /// key -> locales that translate it
+    keys: BTreeMap<String, BTreeSet<String>>,
+    locales: BTreeSet<String>,
+}
+
+/// Vapor's translations and the compiled crate's are checked separately: a key only has to be
+/// translated in the locales of the crate that defines it.
+struct Catalog {
+    vapor: Source,
+    app: Source,
+    stamps: Vec<Stamp>,
+}
+
+/// A locale file with its modification time and length when it was read.
+type Stamp = (PathBuf, Option<(SystemTime, u64)>);
+
+impl Source {
+    fn load(dirs: &[String]) -> Self {
+        let mut source = Source::default();
+        for dir in dirs {
+            collect_files(Path::new(dir), &mut source.files);
+            for (locale, translations) in rust_i18n_support::load_locales(dir, |_| false) {
+                let locale = locale.to_ascii_lowercase();
+                for key in translations.into_keys().filter(|key| !key.starts_with('_')) {
+                    source.keys.entry(key).or_default().insert(locale.clone());
+                }
+                source.locales.insert(locale);
+            }
+        }
+        source.files.sort();
+        source
+    }
+
+    /// Locales of this source that lack `key`, or `None` when the source does not define it.
+    fn missing(&self, key: &str) -> Option<Vec<&str>> {
+        let found = self.keys.get(key)?;
+        Some(self.locales.difference(found).map(String::as_str).collect())
+    }
+}
+
+impl Catalog {
+    /// The compiled crate's locale directories.
+    fn app_dirs(root: &str) -> Vec<String> {
+        // Vapor's own `locale/` is already checked through the copy in `VAPOR_LOCALES`.
+        if env::var("CARGO_PKG_NAME").is_ok_and(|name| name == "vapor") {
+            return Vec::new();
+        }
+        CRATE_LOCALES
+            .iter()
+            .map(|dir| Path::new(root).join(dir))
+            .filter(|path| path.is_dir())
+            .map(|path| path.display().to_string())
+            .collect()
+    }
+
+    fn load(app_dirs: &[String], stamps: Vec<Stamp>) -> Self {
+        Catalog {
+            vapor: Source::load(&[VAPOR_LOCALES.to_string()]),
+            app: Source::load(app_dirs),
+            stamps,
+        }
+    }
+
+    fn check(&self, key: &str) -> Result<(), String> {
+        let sources: Vec<Vec<&str>> = [&self.vapor, &self.app].iter().filter_map(|source| source.missing(key)).collect();
+        if sources.is_empty() {
+            let suggestions = self.suggestions(key);
+            return Err(if suggestions.is_empty() {
+                format!("unknown log key `{}`", key)
+            } else {
+                format!("unknown log key `{}`, did you mean {}?", key, suggestions.join(", "))
+            });
+        }
+
+        let missing: BTreeSet<&str> = sources.into_iter().flatten().collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            let missing: Vec<&str> = missing.into_iter().collect();
+            Err(format!("log key `{}` is not translated in {}", key, missing.join(", ")))
+        }
+    }
+
+    fn suggestions(&self, key: &str) -> Vec<String> {
+        let limit = (key.chars().count() / 3).max(2);
+        let known: BTreeSet<&String> = self.vapor.keys.keys().chain(self.app.keys.keys()).collect();
+        let mut candidates: Vec<(usize, &String)> = known
+            .into_iter()
+            .map(|known| (strsim::levenshtein(key, known), known))
+            .filter(|(distance, _)| *distance <= limit)
+            .collect();
+        candidates.sort();
+        candidates
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, known)| format!("`{}`", known))
+            .collect()
+    }
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else if path.extension().and_then(|ext| ext.to_str()).is_some_and(|ext| EXTENSIONS.contains(&ext)) {
+            files.push(path);
+        }
+    }
+}
+
+fn stamps(dirs: &[String]) -> Vec<Stamp> {
+    let mut files = Vec::new();
+    for dir in dirs {
+        collect_files(Path::new(dir), &mut files);
+    }
+    files.sort();
+    files
+        .into_iter()
+        .map(|path| {
+            let stamp = fs::metadata(&path).ok().and_then(|metadata| Some((metadata.modified().ok()?, metadata.len())));
+            (path, stamp)
+        })
+        .collect()
+}
+
+/// The catalog of the crate being compiled. One macro process can expand many crates and outlive
+/// edits to their locale files (rust-analyzer keeps it running), so catalogs are cached per manifest
+/// directory and reloaded when a file is added, removed or changed.
+fn catalog() -> Arc<Catalog> {
+    static CATALOGS: Mutex<BTreeMap<String, Arc<Catalog>>> = Mutex::new(BTreeMap::new());
+    let root = env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let app_dirs = Catalog::app_dirs(&root);
+    let mut dirs = app_dirs.clone();
+    dirs.push(VAPOR_LOCALES.to_string());
+    let stamps = stamps(&dirs);
+
+    let mut catalogs = CATALOGS.lock().unwrap_or_else(PoisonError::into_inner);
+    match catalogs.get(&root) {
+        Some(catalog) if catalog.stamps == stamps => Arc::clone(catalog),
+        _ => {
+            let catalog = Arc::new(Catalog::load(&app_dirs, stamps));
+            catalogs.insert(root, Arc::clone(&catalog));
+            catalog
+        }
+    }
+}
+
+/// Finds a string literal, looking through the invisible groups `macro_rules!` wraps `$key:expr` in.
+fn literal(tokens: TokenStream2) -> Option<LitStr> {
+    let mut tokens = tokens.into_iter();
+    let token = tokens.next()?;
+    if tokens.next().is_some() {
+        return None;
+    }
+    match token {
+        TokenTree::Group(group) if matches!(group.delimiter(), Delimiter::None | Delimiter::Parenthesis) => {
+            literal(group.stream())
+        }
+        TokenTree::Literal(literal) => syn::parse2(TokenTree::Literal(literal).into()).ok(),
+        _ => None,
+    }
+}
+
+/// Splits `vapor_path; key` at the first top-level `;`.
+fn split_path(input: TokenStream2) -> (TokenStream2, TokenStream2) {
+    let mut tokens = input.into_iter();
+    let path = tokens.by_ref().take_while(|token| !matches!(token, TokenTree::Punct(punct) if punct.as_char() == ';')).collect();
+    (path, tokens.collect())
+}
+
+/// `(name, include_str!(path))` for each of the crate's locale files, named relative to its manifest directory.
+fn embedded(files: &[PathBuf]) -> Vec<TokenStream2> {
+    let root = env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    files
+        .iter()
+        .map(|path| {
+            let name = path.strip_prefix(&root).unwrap_or(path).display().to_string();
+            let path = path.display().to_string();
+            quote! { (#name, include_str!(#path)) }
+        })
+        .collect()
+}
+
+/// Called as `verify_key!($crate; key)`. Expands to the key unchanged, or fails compilation when a
+/// literal key is unknown to Vapor's and the crate's locale files or missing in one of their locales.
+/// Keys that are not string literals are left to be checked at runtime.
+///
+/// The expansion also registers the crate's locale files the first time it runs, so loggers resolve
+/// every key that passed the check.
+#[proc_macro]
+pub fn verify_key(input: TokenStream) -> TokenStream {
+    let (vapor, input) = split_path(input.into());
+    let Some(key) = literal(input.clone()) else {
+        return input.into();
+    };
+    let catalog = catalog();
+    if let Err(message) = catalog.check(&key.value()) {
+        return syn::Error::new(key.span(), message).to_compile_error().into();
+    }
+
+    // Including the locale files makes Cargo rebuild the crate when one of them changes.
+    let files = catalog.vapor.files.iter().map(|path| path.display().to_string());
+    let register = (!catalog.app.files.is_empty()).then(|| {
+        let embedded = embedded(&catalog.app.files);
+        quote! {
+            static LOCALES: ::std::sync::Once = ::std::sync::Once::new();
+            LOCALES.call_once(|| #vapor::catalog::register_checked(env!("CARGO_PKG_NAME"), &[#(#embedded),*]));
+        }
+    });
+    quote! {{
+        #(const _: &[u8] = include_bytes!(#files);)*
+        #register
+        #input
+    }}
+    .into()
+}
+
+/// Expands to `[(name, contents), ...]` for the locale files of the crate being compiled, the ones
+/// `verify_key!` checks its keys against. Names are relative to the crate's manifest directory.
+#[proc_macro]
+pub fn locale_files(_input: TokenStream) -> TokenStream {
+    let files = embedded(&catalog().app.files);
+    quote! { [#(#files),*] }.into()
+}
@@ -0,0 +1,156 @@
+use crate::error::VaporError;
+use crate::locale::MissingPolicy;
+use crate::logger::{LOGGER, LogLevel, Logger};
+use crate::sink::Sink;
+use crate::theme::Theme;
+use crate::timestamp::{Precision, TimeFormat};
+use chrono_tz::Tz;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Default)]
+pub struct LoggerBuilder {
+    level: Option<LogLevel>,
+    language: Option<String>,
+    fallbacks: Vec<String>,
+    missing_policy: Option<MissingPolicy>,
+    timezone: Option<String>,
+    theme: Option<Theme>,
+    time_format: Option<TimeFormat>,
+    precision: Option<Precision>,
+    sinks: Vec<Arc<dyn Sink>>,
+}
+
+pub fn parse_timezone(name: &str) -> Result<Tz, VaporError> {
+    Tz::from_str(name).map_err(|_| VaporError::UnknownTimezone(name.to_string()))
+}
+
+pub fn check_locale(lang: &str) -> Result<(), VaporError> {
+    if crate::_rust_i18n_available_locales()
+        .iter()
+        .any(|locale| locale.eq_ignore_ascii_case(lang))
+    {
+        Ok(())
+    } else {
+        Err(VaporError::UnknownLocale(lang.to_string()))
+    }
+}
+
+impl LoggerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn level(mut self, level: LogLevel) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    pub fn language(mut self, lang: &str) -> Self {
+        self.language = Some(lang.to_string());
+        self
+    }
+
+    pub fn fallbacks(mut self, chain: &[&str]) -> Self {
+        self.fallbacks = chain.iter().map(|locale| locale.to_string()).collect();
+        self
+    }
+
+    pub fn missing_policy(mut self, policy: MissingPolicy) -> Self {
+        self.missing_policy = Some(policy);
+        self
+    }
+
+    pub fn timezone(mut self, tz: &str) -> Self {
+        self.timezone = Some(tz.to_string());
+        self
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    pub fn time_format(mut self, format: TimeFormat) -> Self {
+        self.time_format = Some(format);
+        self
+    }
+
+    pub fn precision(mut self, precision: Precision) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// The first sink replaces the default console sink.
+    pub fn sink(mut self, sink: impl Sink + 'static) -> Self {
+        self.sinks.push(Arc::new(sink));
+        self
+    }
+
+    pub fn shared_sink(mut self, sink: Arc<dyn Sink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    pub fn build(self) -> Result<Logger, VaporError> {
+        let timezone = self.timezone.as_deref().map(parse_timezone).transpose()?;
+        if let Some(lang) = &self.language {
+            check_locale(lang)?;
+        }
+        for locale in &self.fallbacks {
+            check_locale(locale)?;
+        }
+
+        let mut logger = Logger::new();
+        if let Some(level) = self.level {
+            logger.set_min_level(level);
+            logger.apply_env_filter();
+        }
+        if let Some(lang) = &self.language {
+            logger.set_language(lang);
+        }
+        if !self.fallbacks.is_empty() {
+            logger.set_fallbacks(&self.fallbacks.iter().map(String::as_str).collect::<Vec<_>>());
+        }
+        if let Some(policy) = self.missing_policy {
+            logger.set_missing_policy(policy);
+        }
+        if let Some(tz) = timezone {
+            logger.set_timezone(tz);
+        }
+        if let Some(format) = self.time_format {
+            logger.set_time_format(format);
+        }
+        if let Some(precision) = self.precision {
+            logger.set_precision(precision);
+        }
+        if !self.sinks.is_empty() {
+            logger.clear_sinks();
+            for sink in self.sinks {
+                logger.add_shared_sink(sink);
+            }
+        }
+        if let Some(theme) = self.theme {
+            logger.set_theme(theme);
+        }
+        Ok(logger)
+    }
+
+    pub fn install(self) -> Result<(), VaporError> {
+        if INSTALLED.load(Ordering::SeqCst) {
+            return Err(VaporError::AlreadyInitialized);
+        }
+        let logger = self.build()?;
+        if INSTALLED.swap(true, Ordering::SeqCst) {
+            return Err(VaporError::AlreadyInitialized);
+        }
+        let mut global = LOGGER.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        *global = logger;
+        #[cfg(feature = "log")]
+        crate::log_bridge::update_max_level(&global);
+        Ok(())
+    }
+}
@@ -0,0 +1,342 @@
+use lazy_static::lazy_static;
+use rust_i18n::Backend;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+lazy_static! {
+    static ref REGISTERED: RwLock<Registered> = RwLock::new(Registered::default());
+}
+
+/// Source name reported for translations compiled into Vapor.
+const BUILT_IN: &str = "vapor";
+
+#[derive(Default)]
+struct Registered {
+    locales: Vec<&'static str>,
+    /// Crates whose checked locale files were registered by their log calls.
+    crates: Vec<&'static str>,
+    entries: HashMap<(String, String), Entry>,
+}
+
+#[derive(Clone, Copy)]
+struct Entry {
+    text: &'static str,
+    source: &'static str,
+}
+
+/// Serves registered catalogs to `t!` and the logger, next to the translations in `locale/`.
+pub(crate) struct RuntimeBackend;
+
+impl Backend for RuntimeBackend {
+    fn available_locales(&self) -> Vec<&str> {
+        REGISTERED.read().unwrap().locales.clone()
+    }
+
+    fn translate(&self, locale: &str, key: &str) -> Option<&str> {
+        let registered = REGISTERED.read().unwrap();
+        registered
+            .entries
+            .get(&(locale.to_string(), key.to_string()))
+            .map(|entry| entry.text)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CatalogFormat {
+    Yaml,
+    Json,
+    Toml,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Conflict {
+    pub locale: String,
+    pub key: String,
+    pub first: String,
+    pub second: String,
+}
+
+#[derive(Debug)]
+pub enum CatalogError {
+    Io(PathBuf, io::Error),
+    Parse(String, String),
+    Conflicts(Vec<Conflict>),
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CatalogError::Io(path, err) => write!(f, "cannot read catalog {}: {}", path.display(), err),
+            CatalogError::Parse(source, err) => write!(f, "invalid catalog {}: {}", source, err),
+            CatalogError::Conflicts(conflicts) => {
+                let conflicts: Vec<String> = conflicts
+                    .iter()
+                    .map(|conflict| format!("{}.{} ({} and {})", conflict.locale, conflict.key, conflict.first, conflict.second))
+                    .collect();
+                write!(f, "conflicting translations: {}", conflicts.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for CatalogError {}
+
+/// Translations gathered from files and strings, in the same v1 and v2 formats as `locale/app.yml`,
+/// then merged into Vapor's built-in ones with [`Catalog::register`].
+///
+/// With `verify-keys`, literal keys are checked against the crate's `locales/` directory at compile
+/// time, and the first log call with a checked key registers those files.
+#[derive(Clone, Debug, Default)]
+pub struct Catalog {
+    entries: BTreeMap<(String, String), (String, String)>,
+    conflicts: Vec<Conflict>,
+}
+
+impl Catalog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds every `.yml`, `.yaml`, `.json` and `.toml` file under `dir`. A v1 file is read as the locale
+    /// named by the last part of its file stem, e.g. `app.zh-cn.yml`.
+    pub fn add_dir(&mut self, dir: impl AsRef<Path>) -> Result<&mut Self, CatalogError> {
+        let dir = dir.as_ref();
+        let mut files = Vec::new();
+        collect_files(dir, &mut files).map_err(|err| CatalogError::Io(dir.to_path_buf(), err))?;
+        files.sort();
+        for path in files {
+            self.add_file(&path)?;
+        }
+        Ok(self)
+    }
+
+    pub fn add_file(&mut self, path: impl AsRef<Path>) -> Result<&mut Self, CatalogError> {
+        let path = path.as_ref();
+        let name = path.display().to_string();
+        let format = format_of(&name)?;
+        let content = fs::read_to_string(path).map_err(|err| CatalogError::Io(path.to_path_buf(), err))?;
+        self.add_parsed(&name, v1_locale(&name), &content, format)
+    }
+
+    /// Builds a catalog from `(file name, contents)` pairs, such as the ones [`include_locales!`](crate::include_locales)
+    /// embeds. Names are read like the paths given to [`Catalog::add_file`].
+    pub fn from_files(files: &[(&str, &str)]) -> Result<Self, CatalogError> {
+        let mut catalog = Catalog::new();
+        for (name, content) in files {
+            catalog.add_parsed(name, v1_locale(name), content, format_of(name)?)?;
+        }
+        Ok(catalog)
+    }
+
+    /// Adds a v2 catalog, or a v1 catalog for the locale `name`, from a string such as `include_str!` output.
+    pub fn add_str(&mut self, name: &str, content: &str, format: CatalogFormat) -> Result<&mut Self, CatalogError> {
+        self.add_parsed(name, name, content, format)
+    }
+
+    fn add_parsed(&mut self, source: &str, locale: &str, content: &str, format: CatalogFormat) -> Result<&mut Self, CatalogError> {
+        let value: Value = match format {
+            CatalogFormat::Yaml => serde_yaml::from_str(content).map_err(|err| err.to_string()),
+            CatalogFormat::Json => serde_json::from_str(content).map_err(|err| err.to_string()),
+            CatalogFormat::Toml => toml::from_str(content).map_err(|err| err.to_string()),
+        }
+        .map_err(|err| CatalogError::Parse(source.to_string(), err))?;
+
+        let mut translations = Vec::new();
+        if value.get("_version").and_then(Value::as_u64) == Some(2) {
+            flatten_v2("", &value, &mut translations);
+        } else {
+            flatten_v1(locale, "", &value, &mut translations);
+        }
+        for (locale, key, text) in translations {
+            self.insert(source, locale, key, text);
+        }
+        Ok(self)
+    }
+
+    fn insert(&mut self, source: &str, locale: String, key: String, text: String) {
+        match self.entries.get(&(locale.clone(), key.clone())) {
+            Some((existing, first)) if *existing != text => self.conflicts.push(Conflict {
+                locale,
+                key,
+                first: first.clone(),
+                second: source.to_string(),
+            }),
+            Some(_) => {}
+            None => {
+                self.entries.insert((locale, key), (text, source.to_string()));
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Makes the translations available to every logger. Nothing is registered if a key is
+    /// translated differently by two sources, including Vapor itself and earlier catalogs.
+    pub fn register(&self) -> Result<(), CatalogError> {
+        let mut conflicts = self.conflicts.clone();
+        for ((locale, key), (text, source)) in &self.entries {
+            if let Some(first) = built_in_conflict(locale, key, text) {
+                conflicts.push(Conflict {
+                    locale: locale.clone(),
+                    key: key.clone(),
+                    first,
+                    second: source.clone(),
+                });
+            }
+        }
+
+        let mut registered = REGISTERED.write().unwrap();
+        for ((locale, key), (text, source)) in &self.entries {
+            if let Some(entry) = registered.entries.get(&(locale.clone(), key.clone()))
+                && entry.text != text
+            {
+                conflicts.push(Conflict {
+                    locale: locale.clone(),
+                    key: key.clone(),
+                    first: entry.source.to_string(),
+                    second: source.clone(),
+                });
+            }
+        }
+        if !conflicts.is_empty() {
+            return Err(CatalogError::Conflicts(conflicts));
+        }
+
+        for ((locale, key), (text, source)) in &self.entries {
+            let id = (locale.clone(), key.clone());
+            if registered.entries.contains_key(&id) {
+                continue;
+            }
+            if !registered.locales.iter().any(|known| known == locale) {
+                registered.locales.push(leak(locale));
+            }
+            let entry = Entry {
+                text: leak(text),
+                source: leak(source),
+            };
+            registered.entries.insert(id, entry);
+        }
+        Ok(())
+    }
+}
+
+/// Registers the locale files `verify-keys` checked a crate's literal keys against. Log calls with a
+/// checked key run this once per call site; only the first one per crate registers anything.
+#[doc(hidden)]
+pub fn register_checked(krate: &'static str, files: &[(&str, &str)]) {
+    {
+        let mut registered = REGISTERED.write().unwrap();
+        if registered.crates.contains(&krate) {
+            return;
+        }
+        registered.crates.push(krate);
+    }
+    if let Err(err) = Catalog::from_files(files).and_then(|catalog| catalog.register()) {
+        eprintln!("vapor: cannot register the locales of {}: {}", krate, err);
+    }
+}
+
+/// Returns the built-in source when Vapor's own catalog translates `key` differently.
+fn built_in_conflict(locale: &str, key: &str, text: &str) -> Option<String> {
+    if REGISTERED
+        .read()
+        .unwrap()
+        .entries
+        .contains_key(&(locale.to_string(), key.to_string()))
+    {
+        return None;
+    }
+    match crate::_RUST_I18N_BACKEND.translate(locale, key) {
+        Some(existing) if existing != text => Some(BUILT_IN.to_string()),
+        _ => None,
+    }
+}
+
+fn format_of(name: &str) -> Result<CatalogFormat, CatalogError> {
+    match Path::new(name).extension().and_then(|ext| ext.to_str()) {
+        Some("yml") | Some("yaml") => Ok(CatalogFormat::Yaml),
+        Some("json") => Ok(CatalogFormat::Json),
+        Some("toml") => Ok(CatalogFormat::Toml),
+        _ => Err(CatalogError::Parse(name.to_string(), "expected .yml, .yaml, .json or .toml".to_string())),
+    }
+}
+
+/// The last part of the file stem, e.g. `zh-cn` for `app.zh-cn.yml`.
+fn v1_locale(name: &str) -> &str {
+    Path::new(name)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .and_then(|stem| stem.rsplit('.').next())
+        .unwrap_or_default()
+}
+
+fn leak(value: &str) -> &'static str {
+    Box::leak(value.to_string().into_boxed_str())
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else if format_of(&path.display().to_string()).is_ok() {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn join(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+fn flatten_v1(locale: &str, prefix: &str, value: &Value, out: &mut Vec<(String, String, String)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                if prefix.is_empty() && key == "_version" {
+                    continue;
+                }
+                flatten_v1(locale, &join(prefix, key), value, out);
+            }
+        }
+        Value::String(text) => out.push((locale.to_string(), prefix.to_string(), text.clone())),
+        Value::Null => out.push((locale.to_string(), prefix.to_string(), String::new())),
+        other => out.push((locale.to_string(), prefix.to_string(), other.to_string())),
+    }
+}
+
+fn flatten_v2(prefix: &str, value: &Value, out: &mut Vec<(String, String, String)>) {
+    let Value::Object(map) = value else {
+        return;
+    };
+    for (key, value) in map {
+        let Value::Object(children) = value else {
+            continue;
+        };
+        let key = join(prefix, key);
+        for (locale, text) in children {
+            if let Value::String(text) = text {
+                out.push((locale.clone(), key.clone(), text.clone()));
+            }
+        }
+        if children.values().any(Value::is_object) {
+            flatten_v2(&key, value, out);
+        }
+    }
+}
@@ -0,0 +1,449 @@
+use crate::builder::{LoggerBuilder, check_locale, parse_timezone};
+use crate::error::VaporError;
+use crate::file::{FileSink, Period};
+use crate::locale::MissingPolicy;
+use crate::logger::{LogLevel, Logger};
+use crate::sink::{ConsoleSink, Format, LeveledSink, Sink};
+use crate::terminal::Stream;
+use crate::theme::Theme;
+use crate::timestamp::{Precision, TimeFormat};
+use chrono_tz::Tz;
+use serde::Deserialize;
+use serde::de::{self, Deserializer, Visitor};
+use std::env;
+use std::fmt::{self, Debug};
+use std::fs;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+pub const DEFAULT_FILES: [&str; 3] = ["vapor.toml", "vapor.yml", "vapor.yaml"];
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub level: Option<LogLevel>,
+    #[serde(deserialize_with = "language")]
+    pub language: Option<String>,
+    #[serde(deserialize_with = "fallbacks")]
+    pub fallbacks: Vec<String>,
+    pub missing_translation: Option<MissingPolicy>,
+    #[serde(deserialize_with = "timezone")]
+    pub timezone: Option<Tz>,
+    pub time_format: Option<TimeFormat>,
+    pub precision: Option<Precision>,
+    pub theme: Option<String>,
+    pub sinks: Vec<SinkConfig>,
+    #[serde(skip)]
+    base_dir: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SinkKind {
+    Console,
+    File,
+}
+
+/// One entry of `sinks`. Settings that do not apply to the sink's type are rejected.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(remote = "Self", deny_unknown_fields)]
+pub struct SinkConfig {
+    #[serde(rename = "type")]
+    pub kind: SinkKind,
+    #[serde(default)]
+    pub level: Option<LogLevel>,
+    #[serde(default)]
+    pub format: Format,
+    #[serde(default)]
+    pub stream: Option<Stream>,
+    #[serde(default)]
+    pub stderr_from: Option<LogLevel>,
+    /// Relative paths are resolved against the config file's directory.
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+    #[serde(default)]
+    pub max_size: Option<u64>,
+    #[serde(default)]
+    pub period: Option<Period>,
+    #[serde(default)]
+    pub keep: Option<usize>,
+    #[serde(default)]
+    pub compress: Option<bool>,
+}
+
+/// Parses a string from inside the deserializer, so errors point at the value rather than its parent.
+struct ParseStr<T, F> {
+    expecting: &'static str,
+    parse: F,
+    _value: PhantomData<T>,
+}
+
+fn parse_str<'de, D, T, F>(deserializer: D, expecting: &'static str, parse: F) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    F: FnOnce(&str) -> Result<T, String>,
+{
+    deserializer.deserialize_str(ParseStr {
+        expecting,
+        parse,
+        _value: PhantomData,
+    })
+}
+
+impl<'de, T, F: FnOnce(&str) -> Result<T, String>> Visitor<'de> for ParseStr<T, F> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+        (self.parse)(value).map_err(E::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for LogLevel {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        parse_str(deserializer, "a log level", |name| {
+            LogLevel::from_name(name).ok_or_else(|| format!("unknown log level: {}", name))
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for TimeFormat {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        parse_str(deserializer, "a time format", |name| {
+            match name.to_ascii_lowercase().replace('_', "-").as_str() {
+                "vapor" => Ok(TimeFormat::Vapor),
+                "rfc3339" => Ok(TimeFormat::Rfc3339),
+                "iso8601-basic" => Ok(TimeFormat::Iso8601Basic),
+                "epoch-millis" => Ok(TimeFormat::EpochMillis),
+                _ if name.contains('%') => Ok(TimeFormat::Custom(name.to_string())),
+                _ => Err(format!(
+                    "unknown time format: {}, expected vapor, rfc3339, iso8601-basic, epoch-millis or a strftime pattern",
+                    name
+                )),
+            }
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for SinkConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let sink = SinkConfig::deserialize(deserializer)?;
+        sink.validate().map_err(de::Error::custom)?;
+        Ok(sink)
+    }
+}
+
+/// A locale name checked against the available translations.
+struct Locale(String);
+
+impl<'de> Deserialize<'de> for Locale {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        parse_str(deserializer, "a locale", |lang| {
+            check_locale(lang).map_err(|err| err.to_string())?;
+            Ok(Locale(lang.to_string()))
+        })
+    }
+}
+
+fn language<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    Locale::deserialize(deserializer).map(|Locale(lang)| Some(lang))
+}
+
+fn fallbacks<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    let chain = Vec::<Locale>::deserialize(deserializer)?;
+    Ok(chain.into_iter().map(|Locale(lang)| lang).collect())
+}
+
+fn timezone<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Tz>, D::Error> {
+    parse_str(deserializer, "a time zone", |name| parse_timezone(name).map(Some).map_err(|err| err.to_string()))
+}
+
+impl Config {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, VaporError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|err| VaporError::Io(path.to_path_buf(), err))?;
+        let mut config = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Self::from_toml(&content, path)?,
+            Some("yml") | Some("yaml") => Self::from_yaml(&content, path)?,
+            _ => {
+                return Err(VaporError::Config {
+                    source: path.display().to_string(),
+                    line: None,
+                    column: None,
+                    message: "unsupported config file, expected .toml, .yml or .yaml".to_string(),
+                });
+            }
+        };
+        config.base_dir = path.parent().map(Path::to_path_buf);
+        config.apply_env()?;
+        Ok(config)
+    }
+
+    /// Loads the first of `vapor.toml`, `vapor.yml` and `vapor.yaml` found in the working directory.
+    pub fn load_default() -> Result<Option<Self>, VaporError> {
+        match DEFAULT_FILES.iter().map(Path::new).find(|path| path.is_file()) {
+            Some(path) => Self::load(path).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn from_toml(content: &str, path: &Path) -> Result<Self, VaporError> {
+        toml::from_str(content).map_err(|err| {
+            let (line, column) = match err.span() {
+                Some(span) => line_column(content, span.start),
+                None => (None, None),
+            };
+            VaporError::Config {
+                source: path.display().to_string(),
+                line,
+                column,
+                message: err.message().to_string(),
+            }
+        })
+    }
+
+    fn from_yaml(content: &str, path: &Path) -> Result<Self, VaporError> {
+        serde_yaml::from_str(content).map_err(|err| {
+            let mut message = err.to_string();
+            let location = err.location();
+            if let Some(location) = &location {
+                let suffix = format!(" at line {} column {}", location.line(), location.column());
+                if let Some(stripped) = message.strip_suffix(&suffix) {
+                    message = stripped.to_string();
+                }
+            }
+            VaporError::Config {
+                source: path.display().to_string(),
+                line: location.as_ref().map(|location| location.line()),
+                column: location.as_ref().map(|location| location.column()),
+                message,
+            }
+        })
+    }
+
+    /// Applies `VAPOR_LEVEL`, `VAPOR_LANG` and `VAPOR_TZ` on top of the file settings.
+    pub fn apply_env(&mut self) -> Result<(), VaporError> {
+        let invalid = |name: &str, err: String| VaporError::Config {
+            source: format!("${}", name),
+            line: None,
+            column: None,
+            message: err,
+        };
+
+        if let Ok(level) = env::var("VAPOR_LEVEL") {
+            let parsed = LogLevel::from_name(&level);
+            self.level = Some(parsed.ok_or_else(|| invalid("VAPOR_LEVEL", format!("unknown log level: {}", level)))?);
+        }
+        if let Ok(lang) = env::var("VAPOR_LANG") {
+            check_locale(&lang).map_err(|err| invalid("VAPOR_LANG", err.to_string()))?;
+            self.language = Some(lang);
+        }
+        if let Ok(tz) = env::var("VAPOR_TZ") {
+            self.timezone = Some(parse_timezone(&tz).map_err(|err| invalid("VAPOR_TZ", err.to_string()))?);
+        }
+        Ok(())
+    }
+
+    fn fallback_chain(&self) -> Vec<&str> {
+        self.fallbacks.iter().map(String::as_str).collect()
+    }
+
+    pub fn theme(&self) -> Result<Option<Theme>, VaporError> {
+        let Some(name) = &self.theme else {
+            return Ok(None);
+        };
+        if let Some(theme) = Theme::by_name(name) {
+            return Ok(Some(theme));
+        }
+        Ok(Some(Theme::from_file(resolve(self.base_dir.as_deref(), Path::new(name)))?))
+    }
+
+    pub fn sinks(&self) -> Result<Vec<Arc<dyn Sink>>, VaporError> {
+        self.sinks.iter().map(|sink| sink.build(self.base_dir.as_deref())).collect()
+    }
+
+    pub fn builder(&self) -> Result<LoggerBuilder, VaporError> {
+        let mut builder = LoggerBuilder::new();
+        if let Some(level) = self.level {
+            builder = builder.level(level);
+        }
+        if let Some(lang) = &self.language {
+            builder = builder.language(lang);
+        }
+        if !self.fallbacks.is_empty() {
+            builder = builder.fallbacks(&self.fallback_chain());
+        }
+        if let Some(policy) = &self.missing_translation {
+            builder = builder.missing_policy(policy.clone());
+        }
+        if let Some(tz) = self.timezone {
+            builder = builder.timezone(tz.name());
+        }
+        if let Some(format) = &self.time_format {
+            builder = builder.time_format(format.clone());
+        }
+        if let Some(precision) = self.precision {
+            builder = builder.precision(precision);
+        }
+        if let Some(theme) = self.theme()? {
+            builder = builder.theme(theme);
+        }
+        for sink in self.sinks()? {
+            builder = builder.shared_sink(sink);
+        }
+        Ok(builder)
+    }
+
+    pub fn build(&self) -> Result<Logger, VaporError> {
+        self.builder()?.build()
+    }
+
+    pub fn install(&self) -> Result<(), VaporError> {
+        self.builder()?.install()
+    }
+
+    /// Reconfigures an existing logger. Theme and sinks are built before anything is touched,
+    /// so on error the logger keeps its old settings. Settings missing here go back to their defaults.
+    pub fn apply(&self, logger: &mut Logger) -> Result<(), VaporError> {
+        let theme = self.theme()?.unwrap_or_default();
+        let sinks = self.sinks()?;
+
+        logger.set_min_level(self.level.unwrap_or(LogLevel::Debug));
+        logger.apply_env_filter();
+        logger.set_language(self.language.as_deref().unwrap_or("en"));
+        logger.set_fallbacks(&self.fallback_chain());
+        logger.set_missing_policy(self.missing_translation.clone().unwrap_or_default());
+        match self.timezone {
+            Some(tz) => logger.set_timezone(tz),
+            None => logger.clear_timezone(),
+        }
+        logger.set_time_format(self.time_format.clone().unwrap_or_default());
+        logger.set_precision(self.precision.unwrap_or_default());
+        logger.clear_sinks();
+        if sinks.is_empty() {
+            logger.add_sink(ConsoleSink::new());
+        }
+        for sink in sinks {
+            logger.add_shared_sink(sink);
+        }
+        logger.set_theme(theme);
+        Ok(())
+    }
+
+    /// Describes each setting that differs from `previous`, e.g. `level: debug -> info`.
+    pub fn changes(&self, previous: &Config) -> Vec<String> {
+        let mut changes = Vec::new();
+        diff(&mut changes, "level", &previous.level.map(|l| l.as_str()), &self.level.map(|l| l.as_str()));
+        diff(&mut changes, "language", &previous.language, &self.language);
+        if previous.fallbacks != self.fallbacks {
+            changes.push(format!("fallbacks: [{}] -> [{}]", previous.fallbacks.join(", "), self.fallbacks.join(", ")));
+        }
+        diff(&mut changes, "missing_translation", &previous.missing_translation, &self.missing_translation);
+        diff(&mut changes, "timezone", &previous.timezone, &self.timezone);
+        diff(&mut changes, "time_format", &previous.time_format, &self.time_format);
+        diff(&mut changes, "precision", &previous.precision, &self.precision);
+        diff(&mut changes, "theme", &previous.theme, &self.theme);
+        if previous.sinks != self.sinks {
+            changes.push(format!("sinks: {} -> {}", previous.sinks.len(), self.sinks.len()));
+        }
+        changes
+    }
+}
+
+impl SinkConfig {
+    fn validate(&self) -> Result<(), String> {
+        let (kind, foreign): (&str, &[(&str, bool)]) = match self.kind {
+            SinkKind::Console => (
+                "console",
+                &[
+                    ("path", self.path.is_some()),
+                    ("max_size", self.max_size.is_some()),
+                    ("period", self.period.is_some()),
+                    ("keep", self.keep.is_some()),
+                    ("compress", self.compress.is_some()),
+                ],
+            ),
+            SinkKind::File if self.path.is_none() => return Err("missing field `path` for a file sink".to_string()),
+            SinkKind::File => ("file", &[("stream", self.stream.is_some()), ("stderr_from", self.stderr_from.is_some())]),
+        };
+        match foreign.iter().find(|(_, set)| *set) {
+            Some((name, _)) => Err(format!("`{}` does not apply to a {} sink", name, kind)),
+            None => Ok(()),
+        }
+    }
+
+    /// Builds the sink, resolving a relative file path against `base_dir`.
+    pub fn build(&self, base_dir: Option<&Path>) -> Result<Arc<dyn Sink>, VaporError> {
+        self.validate().map_err(|message| VaporError::Config {
+            source: "sinks".to_string(),
+            line: None,
+            column: None,
+            message,
+        })?;
+        match self.kind {
+            SinkKind::Console => {
+                let mut sink = ConsoleSink::new().format(self.format);
+                if let Some(stream) = self.stream {
+                    sink = sink.stream(stream);
+                }
+                if let Some(threshold) = self.stderr_from {
+                    sink = sink.stderr_from(threshold);
+                }
+                Ok(leveled(self.level, sink))
+            }
+            SinkKind::File => {
+                let path = resolve(base_dir, self.path.as_deref().unwrap_or(Path::new("")));
+                let mut sink = FileSink::new(&path)
+                    .map_err(|err| VaporError::Io(path, err))?
+                    .format(self.format)
+                    .compress(self.compress.unwrap_or(false));
+                if let Some(bytes) = self.max_size {
+                    sink = sink.max_size(bytes);
+                }
+                if let Some(period) = self.period {
+                    sink = sink.period(period);
+                }
+                if let Some(files) = self.keep {
+                    sink = sink.keep(files);
+                }
+                Ok(leveled(self.level, sink))
+            }
+        }
+    }
+}
+
+fn resolve(base_dir: Option<&Path>, path: &Path) -> PathBuf {
+    match base_dir {
+        Some(dir) => dir.join(path),
+        None => path.to_path_buf(),
+    }
+}
+
+fn leveled(level: Option<LogLevel>, sink: impl Sink + 'static) -> Arc<dyn Sink> {
+    match level {
+        Some(level) => Arc::new(LeveledSink::new(level, sink)),
+        None => Arc::new(sink),
+    }
+}
+
+fn diff<T: Debug + PartialEq>(changes: &mut Vec<String>, name: &str, old: &Option<T>, new: &Option<T>) {
+    if old == new {
+        return;
+    }
+    let show = |value: &Option<T>| match value {
+        Some(value) => format!("{:?}", value).trim_matches('"').to_string(),
+        None => "default".to_string(),
+    };
+    changes.push(format!("{}: {} -> {}", name, show(old), show(new)));
+}
+
+fn line_column(content: &str, offset: usize) -> (Option<usize>, Option<usize>) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rfind('\n').map(|newline| offset - newline).unwrap_or(offset + 1);
+    (Some(line), Some(column))
+}
@@ -0,0 +1,89 @@
+use crate::field::FieldValue;
+use std::cell::{Cell, RefCell};
+use std::marker::PhantomData;
+use std::thread::{self, JoinHandle};
+
+thread_local! {
+    // Each field is tagged with the guard that added it, so guards may drop in any order.
+    static CONTEXT: RefCell<Vec<(u64, String, FieldValue)>> = const { RefCell::new(Vec::new()) };
+    static NEXT_GUARD: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Removes the fields it added when dropped. Guards are tied to the thread that created them.
+#[must_use = "the context is removed as soon as the guard is dropped"]
+pub struct ContextGuard {
+    id: u64,
+    _not_send: PhantomData<*const ()>,
+}
+
+/// A snapshot of a thread's context, for carrying it into another thread.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Context {
+    fields: Vec<(String, FieldValue)>,
+}
+
+/// Adds `fields` to every record logged on this thread until the guard drops.
+/// Inner scopes win over outer ones for the same key.
+pub fn push(fields: &[(&str, FieldValue)]) -> ContextGuard {
+    let id = NEXT_GUARD.with(|next| next.replace(next.get() + 1));
+    CONTEXT.with(|context| {
+        let fields = fields.iter().map(|(key, value)| (id, key.to_string(), value.clone()));
+        context.borrow_mut().extend(fields);
+    });
+    ContextGuard {
+        id,
+        _not_send: PhantomData,
+    }
+}
+
+/// The fields currently in scope on this thread, outermost first.
+pub fn current() -> Vec<(String, FieldValue)> {
+    CONTEXT.with(|context| {
+        let context = context.borrow();
+        let mut fields: Vec<(String, FieldValue)> = Vec::with_capacity(context.len());
+        for (_, key, value) in context.iter() {
+            match fields.iter_mut().find(|(existing, _)| existing == key) {
+                Some(field) => field.1 = value.clone(),
+                None => fields.push((key.clone(), value.clone())),
+            }
+        }
+        fields
+    })
+}
+
+pub fn capture() -> Context {
+    Context { fields: current() }
+}
+
+/// Spawns a thread that starts with this thread's context.
+pub fn spawn<F, T>(f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let context = capture();
+    thread::spawn(move || context.run(f))
+}
+
+impl Context {
+    pub fn fields(&self) -> &[(String, FieldValue)] {
+        &self.fields
+    }
+
+    /// Puts the captured fields in scope on the current thread.
+    pub fn attach(&self) -> ContextGuard {
+        let fields: Vec<(&str, FieldValue)> = self.fields.iter().map(|(key, value)| (key.as_str(), value.clone())).collect();
+        push(&fields)
+    }
+
+    pub fn run<T>(&self, f: impl FnOnce() -> T) -> T {
+        let _guard = self.attach();
+        f()
+    }
+}
+
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        CONTEXT.with(|context| context.borrow_mut().retain(|(id, _, _)| *id != self.id));
+    }
+}
@@ -0,0 +1,69 @@
+use crate::catalog::CatalogError;
+use crate::theme::ThemeError;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub enum VaporError {
+    UnknownTimezone(String),
+    UnknownLocale(String),
+    DuplicateLevel(String),
+    AlreadyInitialized,
+    Theme(ThemeError),
+    Catalog(CatalogError),
+    Io(PathBuf, io::Error),
+    Config {
+        source: String,
+        line: Option<usize>,
+        column: Option<usize>,
+        message: String,
+    },
+}
+
+impl fmt::Display for VaporError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VaporError::UnknownTimezone(tz) => write!(f, "unknown time zone: {}", tz),
+            VaporError::UnknownLocale(lang) => write!(f, "unknown locale: {}", lang),
+            VaporError::DuplicateLevel(name) => write!(f, "log level {:?} is already defined", name),
+            VaporError::AlreadyInitialized => write!(f, "the global logger is already initialized"),
+            VaporError::Theme(err) => err.fmt(f),
+            VaporError::Catalog(err) => err.fmt(f),
+            VaporError::Io(path, err) => write!(f, "cannot read {}: {}", path.display(), err),
+            VaporError::Config {
+                source,
+                line,
+                column,
+                message,
+            } => match (line, column) {
+                (Some(line), Some(column)) => write!(f, "{}:{}:{}: {}", source, line, column, message),
+                (Some(line), None) => write!(f, "{}:{}: {}", source, line, message),
+                _ => write!(f, "{}: {}", source, message),
+            },
+        }
+    }
+}
+
+impl std::error::Error for VaporError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            VaporError::Theme(err) => Some(err),
+            VaporError::Catalog(err) => Some(err),
+            VaporError::Io(_, err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ThemeError> for VaporError {
+    fn from(err: ThemeError) -> Self {
+        VaporError::Theme(err)
+    }
+}
+
+impl From<CatalogError> for VaporError {
+    fn from(err: CatalogError) -> Self {
+        VaporError::Catalog(err)
+    }
+}
@@ -0,0 +1,103 @@
+use serde_json::{Number, Value};
+use std::fmt;
+
+/// A structured field's value. Integers, floats and booleans keep their type so JSON sinks can write
+/// them unquoted; everything else is stored as text.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FieldValue {
+    Str(String),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Bool(bool),
+}
+
+impl FieldValue {
+    pub fn to_json(&self) -> Value {
+        match self {
+            FieldValue::Str(value) => Value::String(value.clone()),
+            FieldValue::Int(value) => Value::from(*value),
+            FieldValue::UInt(value) => Value::from(*value),
+            FieldValue::Float(value) => Number::from_f64(*value).map(Value::Number).unwrap_or_else(|| Value::String(value.to_string())),
+            FieldValue::Bool(value) => Value::Bool(*value),
+        }
+    }
+}
+
+impl fmt::Display for FieldValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldValue::Str(value) => f.write_str(value),
+            FieldValue::Int(value) => write!(f, "{}", value),
+            FieldValue::UInt(value) => write!(f, "{}", value),
+            FieldValue::Float(value) => write!(f, "{}", value),
+            FieldValue::Bool(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl From<String> for FieldValue {
+    fn from(value: String) -> Self {
+        FieldValue::Str(value)
+    }
+}
+
+impl From<&str> for FieldValue {
+    fn from(value: &str) -> Self {
+        FieldValue::Str(value.to_string())
+    }
+}
+
+impl From<bool> for FieldValue {
+    fn from(value: bool) -> Self {
+        FieldValue::Bool(value)
+    }
+}
+
+macro_rules! from_number {
+    ($variant:ident($as:ty): $($ty:ty),*) => {
+        $(impl From<$ty> for FieldValue {
+            fn from(value: $ty) -> Self {
+                FieldValue::$variant(value as $as)
+            }
+        })*
+    };
+}
+
+from_number!(Int(i64): i8, i16, i32, i64, isize);
+from_number!(UInt(u64): u8, u16, u32, u64, usize);
+from_number!(Float(f64): f32, f64);
+
+// The field macros pick a conversion by autoref: `(&Field(&value)).field_value()` finds `Typed` for
+// numbers and booleans before it has to borrow again to reach the `Display` fallback.
+
+#[doc(hidden)]
+pub struct Field<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait Typed {
+    fn field_value(&self) -> FieldValue;
+}
+
+#[doc(hidden)]
+pub trait Displayed {
+    fn field_value(&self) -> FieldValue;
+}
+
+macro_rules! typed {
+    ($($ty:ty),*) => {
+        $(impl Typed for Field<'_, $ty> {
+            fn field_value(&self) -> FieldValue {
+                FieldValue::from(*self.0)
+            }
+        })*
+    };
+}
+
+typed!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64, bool);
+
+impl<T: fmt::Display + ?Sized> Displayed for &Field<'_, T> {
+    fn field_value(&self) -> FieldValue {
+        FieldValue::Str(self.0.to_string())
+    }
+}
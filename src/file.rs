@@ -0,0 +1,215 @@
+use crate::logger::Record;
+use crate::sink::{Format, Sink};
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, Timelike};
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use serde::Deserialize;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Period {
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl Period {
+    fn start_of(self, time: &DateTime<FixedOffset>) -> NaiveDateTime {
+        let local = time.naive_local();
+        let date = local.date();
+        match self {
+            Period::Hourly => date.and_hms_opt(local.hour(), 0, 0).unwrap(),
+            Period::Daily => date.and_hms_opt(0, 0, 0).unwrap(),
+            Period::Weekly => {
+                let monday = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+                monday.and_hms_opt(0, 0, 0).unwrap()
+            }
+            Period::Monthly => NaiveDate::from_ymd_opt(date.year(), date.month(), 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+        }
+    }
+}
+
+pub struct FileSink {
+    state: Mutex<FileState>,
+}
+
+struct FileState {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    modified: Option<SystemTime>,
+    current_period: Option<NaiveDateTime>,
+    max_size: Option<u64>,
+    period: Option<Period>,
+    keep: usize,
+    compress: bool,
+    format: Format,
+}
+
+impl FileSink {
+    pub fn new(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let file = open_append(&path)?;
+        let metadata = file.metadata()?;
+        let modified = if metadata.len() > 0 { metadata.modified().ok() } else { None };
+        Ok(Self {
+            state: Mutex::new(FileState {
+                path,
+                file,
+                size: metadata.len(),
+                modified,
+                current_period: None,
+                max_size: None,
+                period: None,
+                keep: 5,
+                compress: false,
+                format: Format::Text,
+            }),
+        })
+    }
+
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.state.get_mut().unwrap().max_size = Some(bytes);
+        self
+    }
+
+    pub fn period(mut self, period: Period) -> Self {
+        self.state.get_mut().unwrap().period = Some(period);
+        self
+    }
+
+    pub fn keep(mut self, files: usize) -> Self {
+        self.state.get_mut().unwrap().keep = files;
+        self
+    }
+
+    pub fn compress(mut self, compress: bool) -> Self {
+        self.state.get_mut().unwrap().compress = compress;
+        self
+    }
+
+    pub fn format(mut self, format: Format) -> Self {
+        self.state.get_mut().unwrap().format = format;
+        self
+    }
+}
+
+impl Sink for FileSink {
+    fn write(&self, record: &Record) {
+        let mut state = self.state.lock().unwrap();
+        let line = format!("{}\n", state.format.render(record));
+        if let Err(err) = state.write_line(record, line.as_bytes()) {
+            eprintln!("vapor: failed to write log file {}: {}", state.path.display(), err);
+        }
+    }
+
+    fn flush(&self) {
+        let _ = self.state.lock().unwrap().file.flush();
+    }
+}
+
+impl FileState {
+    fn write_line(&mut self, record: &Record, line: &[u8]) -> io::Result<()> {
+        if self.should_rotate(record, line.len() as u64) {
+            self.rotate()?;
+        }
+        self.file.write_all(line)?;
+        self.size += line.len() as u64;
+        Ok(())
+    }
+
+    fn should_rotate(&mut self, record: &Record, incoming: u64) -> bool {
+        let mut rotate = false;
+
+        if let Some(period) = self.period {
+            let start = period.start_of(&record.time);
+            let previous = match self.current_period {
+                Some(previous) => Some(previous),
+                None => self.modified.take().map(|modified| {
+                    let modified = DateTime::<chrono::Utc>::from(modified).with_timezone(record.time.offset());
+                    period.start_of(&modified)
+                }),
+            };
+            if previous.is_some_and(|previous| previous != start) {
+                rotate = true;
+            }
+            self.current_period = Some(start);
+        }
+
+        if let Some(max_size) = self.max_size
+            && self.size + incoming > max_size
+        {
+            rotate = true;
+        }
+
+        rotate && self.size > 0
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+
+        if self.keep == 0 {
+            fs::remove_file(&self.path)?;
+        } else {
+            for index in (1..=self.keep).rev() {
+                for compressed in [false, true] {
+                    let from = rotated_path(&self.path, index, compressed);
+                    if !from.exists() {
+                        continue;
+                    }
+                    if index == self.keep {
+                        fs::remove_file(&from)?;
+                    } else {
+                        fs::rename(&from, rotated_path(&self.path, index + 1, compressed))?;
+                    }
+                }
+            }
+
+            let first = rotated_path(&self.path, 1, false);
+            fs::rename(&self.path, &first)?;
+            if self.compress {
+                gzip(&first, &rotated_path(&self.path, 1, true))?;
+                fs::remove_file(&first)?;
+            }
+        }
+
+        self.file = open_append(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
+    }
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+fn rotated_path(path: &Path, index: usize, compressed: bool) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    if compressed {
+        name.push(".gz");
+    }
+    PathBuf::from(name)
+}
+
+fn gzip(from: &Path, to: &Path) -> io::Result<()> {
+    let mut input = File::open(from)?;
+    let mut encoder = GzEncoder::new(File::create(to)?, Compression::default());
+    io::copy(&mut input, &mut encoder)?;
+    encoder.finish()?;
+    Ok(())
+}
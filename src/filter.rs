@@ -0,0 +1,92 @@
+use crate::logger::LogLevel;
+use std::env;
+use std::fmt;
+
+pub const ENV_VAR: &str = "VAPOR_LOG";
+
+#[derive(Clone, Debug)]
+pub struct Filter {
+    default: LogLevel,
+    directives: Vec<(String, LogLevel)>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseFilterError {
+    directive: String,
+}
+
+impl fmt::Display for ParseFilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid log directive: {:?}", self.directive)
+    }
+}
+
+impl std::error::Error for ParseFilterError {}
+
+impl Filter {
+    pub fn new(default: LogLevel) -> Self {
+        Self {
+            default,
+            directives: Vec::new(),
+        }
+    }
+
+    pub fn directive(mut self, target: &str, level: LogLevel) -> Self {
+        self.directives.retain(|(existing, _)| existing != target);
+        self.directives.push((target.to_string(), level));
+        self.directives.sort_by_key(|(target, _)| std::cmp::Reverse(target.len()));
+        self
+    }
+
+    pub fn set_default(&mut self, level: LogLevel) {
+        self.default = level;
+    }
+
+    pub fn default_level(&self) -> LogLevel {
+        self.default
+    }
+
+    pub fn parse(spec: &str, default: LogLevel) -> Result<Self, ParseFilterError> {
+        let mut filter = Self::new(default);
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            let error = || ParseFilterError {
+                directive: directive.to_string(),
+            };
+            match directive.split_once('=') {
+                Some((target, level)) => {
+                    let level = LogLevel::from_name(level.trim()).ok_or_else(error)?;
+                    filter = filter.directive(target.trim(), level);
+                }
+                None => filter.default = LogLevel::from_name(directive).ok_or_else(error)?,
+            }
+        }
+        Ok(filter)
+    }
+
+    pub fn from_env(default: LogLevel) -> Result<Option<Self>, ParseFilterError> {
+        match env::var(ENV_VAR) {
+            Ok(spec) => Self::parse(&spec, default).map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    pub fn level_for(&self, target: &str) -> LogLevel {
+        self.directives
+            .iter()
+            .find(|(prefix, _)| {
+                target == prefix
+                    || (target.starts_with(prefix.as_str()) && target[prefix.len()..].starts_with("::"))
+            })
+            .map(|(_, level)| *level)
+            .unwrap_or(self.default)
+    }
+
+    /// The most verbose level any target can log at.
+    pub fn min_level(&self) -> LogLevel {
+        self.directives.iter().map(|(_, level)| *level).fold(self.default, std::cmp::min)
+    }
+
+    pub fn enabled(&self, target: &str, level: LogLevel) -> bool {
+        level >= self.level_for(target)
+    }
+}
@@ -0,0 +1,74 @@
+use crate::error::VaporError;
+use crate::logger::LogLevel;
+use lazy_static::lazy_static;
+use std::sync::RwLock;
+
+lazy_static! {
+    static ref LEVELS: RwLock<Vec<LevelInfo>> = RwLock::new(Vec::new());
+}
+
+/// Names `LogLevel::from_name` already resolves to a built-in level.
+const BUILT_IN: [&str; 7] = ["trace", "debug", "info", "warn", "warning", "error", "fatal"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CustomLevel {
+    id: u16,
+    severity: u16,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct LevelInfo {
+    pub name: &'static str,
+    pub severity: u16,
+    pub color: (u8, u8, u8),
+    pub label_key: &'static str,
+}
+
+impl CustomLevel {
+    pub fn severity(&self) -> u16 {
+        self.severity
+    }
+
+    pub fn info(&self) -> LevelInfo {
+        LEVELS.read().unwrap()[self.id as usize]
+    }
+
+    pub(crate) fn id(&self) -> u16 {
+        self.id
+    }
+}
+
+/// Registers a level ordered by `severity` against the built-in ones
+/// (trace 0, debug 100, info 200, warning 300, error 400, fatal 500).
+/// Names are case-insensitive and may not repeat a built-in or already registered level.
+pub fn register_level(name: &str, severity: u16, color: (u8, u8, u8), label_key: &str) -> Result<LogLevel, VaporError> {
+    let mut levels = LEVELS.write().unwrap();
+    let taken = BUILT_IN.iter().any(|built_in| built_in.eq_ignore_ascii_case(name));
+    if taken || levels.iter().any(|level| level.name.eq_ignore_ascii_case(name)) {
+        return Err(VaporError::DuplicateLevel(name.to_string()));
+    }
+
+    levels.push(LevelInfo {
+        name: Box::leak(name.to_string().into_boxed_str()),
+        severity,
+        color,
+        label_key: Box::leak(label_key.to_string().into_boxed_str()),
+    });
+    Ok(LogLevel::Custom(CustomLevel {
+        id: (levels.len() - 1) as u16,
+        severity,
+    }))
+}
+
+pub(crate) fn find(name: &str) -> Option<LogLevel> {
+    let levels = LEVELS.read().unwrap();
+    levels
+        .iter()
+        .position(|level| level.name.eq_ignore_ascii_case(name))
+        .map(|id| {
+            LogLevel::Custom(CustomLevel {
+                id: id as u16,
+                severity: levels[id].severity,
+            })
+        })
+}
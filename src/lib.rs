@@ -0,0 +1,30 @@
+rust_i18n::i18n!("locale", backend = crate::catalog::RuntimeBackend);
+
+#[cfg(feature = "verify-keys")]
+#[doc(hidden)]
+pub use vapor_macros::verify_key as __verify_key;
+#[cfg(feature = "verify-keys")]
+#[doc(hidden)]
+pub use vapor_macros::locale_files as __locale_files;
+
+pub mod builder;
+pub mod catalog;
+pub mod config;
+pub mod context;
+pub mod error;
+pub mod field;
+pub mod file;
+pub mod filter;
+pub mod level;
+pub mod locale;
+#[cfg(feature = "log")]
+pub mod log_bridge;
+pub mod logger;
+pub mod reload;
+pub mod sink;
+pub mod terminal;
+pub mod theme;
+pub mod timestamp;
+#[cfg(feature = "tracing")]
+pub mod tracing_layer;
+pub mod writer;
@@ -0,0 +1,82 @@
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Mutex;
+
+lazy_static! {
+    static ref MISSING: Mutex<BTreeSet<(String, String)>> = Mutex::new(BTreeSet::new());
+}
+
+/// What `Logger::log` prints when no locale in its chain has the key.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MissingPolicy {
+    /// The bilingual "翻译失败！Translate Failed!" line with language, time zone and key.
+    #[default]
+    Verbose,
+    RawKey,
+    /// Fixed text in which `%{key}` and `%{locale}` are replaced.
+    Fallback(String),
+    /// Panics in debug builds and prints the raw key in release builds.
+    Panic,
+    /// Prints the raw key and logs a separate warning about it.
+    Warn,
+}
+
+/// Looks `key` up in exactly `locale` (matched case-insensitively), without rust-i18n's own fallbacks.
+pub fn lookup(locale: &str, key: &str) -> Option<String> {
+    let backend = &crate::_RUST_I18N_BACKEND;
+    if let Some(text) = backend.translate(locale, key) {
+        return Some(text.to_string());
+    }
+    let available = backend.available_locales();
+    let matched = available.iter().find(|available| available.eq_ignore_ascii_case(locale))?;
+    backend.translate(matched, key).map(str::to_string)
+}
+
+/// Returns the first translation of `key` along `chain`, with the locale it came from.
+pub fn resolve<'a>(chain: impl IntoIterator<Item = &'a str>, key: &str) -> Option<(String, &'a str)> {
+    chain.into_iter().find_map(|locale| lookup(locale, key).map(|text| (text, locale)))
+}
+
+/// Like [`resolve`], but remembers every locale that had to be skipped.
+pub(crate) fn translate<'a>(chain: impl IntoIterator<Item = &'a str>, key: &str) -> Option<(String, &'a str)> {
+    let mut skipped = Vec::new();
+    let mut found = None;
+    for locale in chain {
+        match lookup(locale, key) {
+            Some(text) => {
+                found = Some((text, locale));
+                break;
+            }
+            None => skipped.push((key.to_string(), locale.to_ascii_lowercase())),
+        }
+    }
+    if !skipped.is_empty() {
+        MISSING.lock().unwrap().extend(skipped);
+    }
+    found
+}
+
+/// Every `(key, locale)` pair that was looked up and not found so far.
+pub fn missing() -> Vec<(String, String)> {
+    MISSING.lock().unwrap().iter().cloned().collect()
+}
+
+pub fn clear_missing() {
+    MISSING.lock().unwrap().clear();
+}
+
+/// The missing pairs as a locale file stub with empty translations, in the format of `locale/app.yml`.
+pub fn missing_yaml() -> String {
+    let mut keys: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+    for (key, locale) in MISSING.lock().unwrap().iter() {
+        keys.entry(key.clone()).or_default().insert(locale.clone(), String::new());
+    }
+    let body = if keys.is_empty() {
+        String::new()
+    } else {
+        serde_yaml::to_string(&keys).unwrap_or_default()
+    };
+    format!("_version: 2\n\n{}", body)
+}
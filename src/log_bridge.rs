@@ -0,0 +1,57 @@
+use crate::logger::{LOGGER, LogLevel, Logger};
+use log::{Level, LevelFilter, Metadata, Record, SetLoggerError};
+
+pub struct VaporLog;
+
+static VAPOR_LOG: VaporLog = VaporLog;
+
+pub fn install() -> Result<(), SetLoggerError> {
+    log::set_logger(&VAPOR_LOG)?;
+    update_max_level(&LOGGER.lock().unwrap_or_else(|poisoned| poisoned.into_inner()));
+    Ok(())
+}
+
+/// Lets the `log` macros skip records `logger` would drop anyway. [`install`], `LoggerBuilder::install`
+/// and the reload watcher call this; call it again after changing the global logger's filter by hand.
+pub fn update_max_level(logger: &Logger) {
+    let min_level = logger.filter().min_level();
+    let max_level = [Level::Trace, Level::Debug, Level::Info, Level::Warn, Level::Error]
+        .into_iter()
+        .find(|level| LogLevel::from(*level) >= min_level)
+        .map(|level| level.to_level_filter())
+        .unwrap_or(LevelFilter::Off);
+    log::set_max_level(max_level);
+}
+
+impl From<Level> for LogLevel {
+    fn from(level: Level) -> Self {
+        match level {
+            Level::Error => LogLevel::Error,
+            Level::Warn => LogLevel::Warning,
+            Level::Info => LogLevel::Info,
+            Level::Debug => LogLevel::Debug,
+            Level::Trace => LogLevel::Trace,
+        }
+    }
+}
+
+impl log::Log for VaporLog {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        LOGGER.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).enabled(metadata.target(), metadata.level().into())
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let message = record.args().to_string();
+        LOGGER
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .log_message(record.level().into(), record.target(), &message, &[]);
+    }
+
+    fn flush(&self) {
+        LOGGER.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).flush();
+    }
+}
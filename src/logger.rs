@@ -1,170 +1,601 @@
-use chrono::{Local, TimeZone};
+use crate::context;
+use crate::field::FieldValue;
+use crate::filter::Filter;
+use crate::level::{self, CustomLevel};
+use crate::locale::{self, MissingPolicy};
+use crate::sink::{ConsoleSink, Sink};
+use crate::theme::Theme;
+use crate::timestamp::{Precision, TimeFormat};
+use crate::writer::{Overflow, Queue, Sinks, WriterGuard};
+use chrono::{DateTime, FixedOffset, Local, Utc};
 use chrono_tz::Tz;
-use colored::Colorize;
 use lazy_static::lazy_static;
-use rust_i18n::t;
-use std::sync::Mutex;
+use std::cmp::Ordering;
+use std::sync::{Arc, Mutex};
 
 lazy_static! {
     pub static ref LOGGER: Mutex<Logger> = Mutex::new(Logger::new());
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum LogLevel {
+    Trace,
     Debug,
     Info,
     Warning,
     Error,
+    Fatal,
+    Custom(CustomLevel),
 }
 
+impl LogLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Trace => "trace",
+            LogLevel::Debug => "debug",
+            LogLevel::Info => "info",
+            LogLevel::Warning => "warning",
+            LogLevel::Error => "error",
+            LogLevel::Fatal => "fatal",
+            LogLevel::Custom(level) => level.info().name,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "trace" => Some(LogLevel::Trace),
+            "debug" => Some(LogLevel::Debug),
+            "info" => Some(LogLevel::Info),
+            "warn" | "warning" => Some(LogLevel::Warning),
+            "error" => Some(LogLevel::Error),
+            "fatal" => Some(LogLevel::Fatal),
+            _ => level::find(name),
+        }
+    }
+
+    pub fn severity(&self) -> u16 {
+        match self {
+            LogLevel::Trace => 0,
+            LogLevel::Debug => 100,
+            LogLevel::Info => 200,
+            LogLevel::Warning => 300,
+            LogLevel::Error => 400,
+            LogLevel::Fatal => 500,
+            LogLevel::Custom(level) => level.severity(),
+        }
+    }
+
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            LogLevel::Custom(level) => level.info().label_key,
+            _ => self.as_str(),
+        }
+    }
+
+    fn sort_key(&self) -> (u16, u32) {
+        match self {
+            LogLevel::Custom(level) => (level.severity(), level.id() as u32 + 1),
+            _ => (self.severity(), 0),
+        }
+    }
+}
+
+impl PartialOrd for LogLevel {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for LogLevel {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Record {
+    pub time: DateTime<FixedOffset>,
+    pub timestamp: String,
+    pub level: LogLevel,
+    /// The level's translated name, resolved along the logger's locale chain.
+    pub label: String,
+    pub target: String,
+    pub key: String,
+    pub message: String,
+    pub language: String,
+    /// The locale the message was translated from. Differs from `language` when a fallback was used.
+    pub locale: String,
+    pub fields: Vec<(String, FieldValue)>,
+    /// The theme of the logger that wrote the record, for sinks that color their output.
+    pub theme: Arc<Theme>,
+}
+
+/// A logger that can be owned and passed around. The logging macros use the global [`LOGGER`]
+/// unless given one with `logger: <expr>,` first.
+#[derive(Clone)]
 pub struct Logger {
     timezone: Option<Tz>,
     language: Option<String>,
-    min_level: LogLevel,
+    fallbacks: Vec<String>,
+    missing_policy: MissingPolicy,
+    time_format: TimeFormat,
+    precision: Precision,
+    filter: Filter,
+    fatal_exit_code: i32,
+    theme: Arc<Theme>,
+    sinks: Sinks,
+    writer: Option<Arc<Queue>>,
+    context: Vec<(String, FieldValue)>,
 }
 
 impl Logger {
-    fn new() -> Self {
-        Self {
+    pub fn new() -> Self {
+        let mut logger = Self {
             timezone: None,
             language: Some("en".to_string()),
-            min_level: LogLevel::Debug,
+            fallbacks: Vec::new(),
+            missing_policy: MissingPolicy::default(),
+            time_format: TimeFormat::Vapor,
+            precision: Precision::Seconds,
+            filter: Filter::new(LogLevel::Debug),
+            fatal_exit_code: 1,
+            theme: Arc::new(Theme::default()),
+            sinks: Arc::new(vec![Arc::new(ConsoleSink::new())]),
+            writer: None,
+            context: Vec::new(),
+        };
+        logger.apply_env_filter();
+        logger
+    }
+
+    /// Copies this logger's settings and sinks, adding `fields` to every record it writes.
+    /// Later changes to the parent are not seen by the child.
+    pub fn child(&self, fields: &[(&str, FieldValue)]) -> Logger {
+        let mut child = self.clone();
+        for (key, value) in fields {
+            child.context.retain(|(existing, _)| existing != key);
+            child.context.push((key.to_string(), value.clone()));
         }
+        child
+    }
+
+    pub fn context(&self) -> &[(String, FieldValue)] {
+        &self.context
     }
 
     pub fn set_min_level(&mut self, level: LogLevel) {
-        self.min_level = level;
+        self.filter.set_default(level);
+    }
+
+    pub fn set_filter(&mut self, filter: Filter) {
+        self.filter = filter;
+    }
+
+    pub fn filter(&self) -> &Filter {
+        &self.filter
+    }
+
+    pub fn apply_env_filter(&mut self) {
+        match Filter::from_env(self.filter.default_level()) {
+            Ok(Some(filter)) => self.filter = filter,
+            Ok(None) => {}
+            Err(err) => eprintln!("vapor: ignoring {}: {}", crate::filter::ENV_VAR, err),
+        }
     }
 
     pub fn set_timezone(&mut self, tz: Tz) {
         self.timezone = Some(tz);
     }
 
+    /// Goes back to the system's local time zone.
+    pub fn clear_timezone(&mut self) {
+        self.timezone = None;
+    }
+
     pub fn set_language(&mut self, lang: &str) {
-        rust_i18n::set_locale(lang);
         self.language = Some(lang.to_string());
     }
 
-    fn format_time(&self) -> String {
+    /// Locales tried in order when a key is missing in the logger's language, e.g. `["zh-cn", "en"]` for zh-tw.
+    pub fn set_fallbacks(&mut self, chain: &[&str]) {
+        self.fallbacks = chain.iter().map(|locale| locale.to_string()).collect();
+    }
+
+    pub fn set_missing_policy(&mut self, policy: MissingPolicy) {
+        self.missing_policy = policy;
+    }
+
+    pub fn set_time_format(&mut self, format: TimeFormat) {
+        self.time_format = format;
+    }
+
+    pub fn set_precision(&mut self, precision: Precision) {
+        self.precision = precision;
+    }
+
+    pub fn set_fatal_exit_code(&mut self, code: i32) {
+        self.fatal_exit_code = code;
+    }
+
+    /// Sets the theme this logger's records are colored with. Sinks shared with a parent or child
+    /// logger keep coloring that logger's records with its own theme.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = Arc::new(theme);
+    }
+
+    pub fn add_sink(&mut self, sink: impl Sink + 'static) {
+        self.add_shared_sink(Arc::new(sink));
+    }
+
+    pub fn add_shared_sink(&mut self, sink: Arc<dyn Sink>) {
+        Arc::make_mut(&mut self.sinks).push(sink);
+    }
+
+    pub fn clear_sinks(&mut self) {
+        self.sinks = Arc::new(Vec::new());
+    }
+
+    pub fn spawn_writer(&mut self, capacity: usize, overflow: Overflow) -> WriterGuard {
+        let (queue, guard) = Queue::spawn(capacity, overflow);
+        self.writer = Some(queue);
+        guard
+    }
+
+    pub fn dropped_records(&self) -> u64 {
+        self.writer.as_ref().map(|queue| queue.dropped()).unwrap_or(0)
+    }
+
+    pub fn time_at(&self, instant: DateTime<Utc>) -> DateTime<FixedOffset> {
         match self.timezone {
-            Some(ref tz) => {
-                let dt = tz.from_local_datetime(&Local::now().naive_local()).unwrap();
-                dt.format("%Y/%-m/%-d %H:%M:%S").to_string()
+            Some(ref tz) => instant.with_timezone(tz).fixed_offset(),
+            None => instant.with_timezone(&Local).fixed_offset(),
+        }
+    }
+
+    fn now(&self) -> DateTime<FixedOffset> {
+        self.time_at(Utc::now())
+    }
+
+    pub fn log(&self, level: LogLevel, target: &str, key: &str, args: &[(&str, String)], fields: &[(&str, FieldValue)]) {
+        if let Err(message) = self.try_log(level, target, key, args, fields) {
+            panic!("{}", message);
+        }
+    }
+
+    /// Like [`Logger::log`], but returns the message [`MissingPolicy::Panic`] would panic with, so the
+    /// caller can release its lock on the logger before panicking.
+    pub fn try_log(&self, level: LogLevel, target: &str, key: &str, args: &[(&str, String)], fields: &[(&str, FieldValue)]) -> Result<(), String> {
+        if !self.enabled(target, level) {
+            return Ok(());
+        }
+
+        let lang = self.language.as_deref().unwrap_or("en");
+        let Some((template, locale)) = locale::translate(self.locales(), key) else {
+            if self.missing_policy == MissingPolicy::Panic && cfg!(debug_assertions) {
+                return Err(format!("missing {} translation for {}", lang, key));
             }
-            None => {
-                let dt = Local::now();
-                dt.format("%Y/%-m/%-d %H:%M:%S").to_string()
+            let message = self.missing_message(key, lang);
+            self.dispatch(level, target, key, message, lang, fields);
+            if self.missing_policy == MissingPolicy::Warn && self.enabled(target, LogLevel::Warning) {
+                let args = [("key", key.to_string()), ("locale", lang.to_string())];
+                let (message, locale) = match locale::resolve(self.locales(), "translation.missing") {
+                    Some((template, locale)) => (interpolate(&template, &args), locale),
+                    None => (format!("Missing {} translation for {}", lang, key), lang),
+                };
+                self.dispatch(LogLevel::Warning, target, "translation.missing", message, locale, &[]);
+            }
+            return Ok(());
+        };
+
+        self.dispatch(level, target, key, interpolate(&template, args), locale, fields);
+        Ok(())
+    }
+
+    fn missing_message(&self, key: &str, lang: &str) -> String {
+        match &self.missing_policy {
+            MissingPolicy::Verbose => {
+                let tz_str = self.timezone.as_ref().map(|tz| tz.name()).unwrap_or("unknown");
+                format!("翻译失败！Translate Failed! | 语言 Lang {} | 时区 Tz {} | 内容 Value {}", lang, tz_str, key)
             }
+            MissingPolicy::Fallback(text) => interpolate(text, &[("key", key.to_string()), ("locale", lang.to_string())]),
+            MissingPolicy::RawKey | MissingPolicy::Panic | MissingPolicy::Warn => key.to_string(),
+        }
+    }
+
+    pub fn fatal(&self, target: &str, key: &str, args: &[(&str, String)], fields: &[(&str, FieldValue)]) -> ! {
+        if let Err(message) = self.try_log(LogLevel::Fatal, target, key, args, fields) {
+            self.log_message(LogLevel::Fatal, target, &message, fields);
         }
+        self.flush();
+        std::process::exit(self.fatal_exit_code)
     }
 
-    pub fn log(&self, level: LogLevel, key: &str) {
-        if level < self.min_level {
+    pub fn log_message(&self, level: LogLevel, target: &str, message: &str, fields: &[(&str, FieldValue)]) {
+        if !self.enabled(target, level) {
             return;
         }
 
-        let time = self.format_time();
-
-        let (level_color, message_color, level_str) = match level {
-            LogLevel::Error => (
-                (255, 46, 99),
-                (255, 46, 99),
-                t!("error").to_string(),
-            ),
-            LogLevel::Warning => (
-                (249, 237, 105),
-                (249, 237, 105),
-                t!("warning").to_string(),
-            ),
-            LogLevel::Info => (
-                (48, 227, 202),
-                (255, 255, 255),
-                t!("info").to_string(),
-            ),
-            LogLevel::Debug => (
-                (82, 97, 107),
-                (82, 97, 107),
-                t!("debug").to_string(),
-            ),
-        };
+        let lang = self.language.as_deref().unwrap_or("en");
+        self.dispatch(level, target, message, message.to_string(), lang, fields);
+    }
+
+    pub fn has_key(&self, key: &str) -> bool {
+        locale::resolve(self.locales(), key).is_some()
+    }
 
-        let level_display = format!("[{}] ", level_str).truecolor(level_color.0, level_color.1, level_color.2);
+    /// The logger's language followed by its fallbacks.
+    pub fn locales(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.language.as_deref().unwrap_or("en")).chain(self.fallbacks.iter().map(String::as_str))
+    }
+
+    pub fn enabled(&self, target: &str, level: LogLevel) -> bool {
+        self.filter.enabled(target, level)
+    }
+
+    pub fn flush(&self) {
+        if let Some(queue) = &self.writer
+            && queue.flush(Arc::clone(&self.sinks))
+        {
+            return;
+        }
+        for sink in self.sinks.iter() {
+            sink.flush();
+        }
+    }
+
+    fn dispatch(&self, level: LogLevel, target: &str, key: &str, message: String, locale: &str, fields: &[(&str, FieldValue)]) {
+        let time = self.now();
+        let record = Record {
+            timestamp: self.time_format.format(&time, self.precision),
+            time,
+            level,
+            label: locale::resolve(self.locales(), level.label_key())
+                .map(|(label, _)| label)
+                .unwrap_or_else(|| level.label_key().to_string()),
+            target: target.to_string(),
+            key: key.to_string(),
+            message,
+            language: self.language.clone().unwrap_or_default(),
+            locale: locale.to_string(),
+            fields: self
+                .context
+                .iter()
+                .cloned()
+                .chain(context::current())
+                .chain(fields.iter().map(|(key, value)| (key.to_string(), value.clone())))
+                .collect(),
+            theme: Arc::clone(&self.theme),
+        };
 
-        let raw_message = t!(key);
-        let final_message = if raw_message == key {
-            let lang = self.language.as_ref().unwrap();
-            let tz_str = self.timezone.as_ref().map(|tz| tz.name()).unwrap_or("unknown");
-            format!("翻译失败！Translate Failed! | 语言 Lang {} | 时区 Tz {} | 内容 Value {}", lang, tz_str, key)
-        } else {
-            raw_message.into_owned()
+        let record = match &self.writer {
+            Some(queue) => match queue.push(record, Arc::clone(&self.sinks)) {
+                Some(record) => record,
+                None => return,
+            },
+            None => record,
         };
-        let colored_message = final_message.truecolor(message_color.0, message_color.1, message_color.2);
 
-        println!("{} {}{}", time, level_display, colored_message);
+        for sink in self.sinks.iter() {
+            sink.write(&record);
+        }
+    }
+}
+
+impl Default for Logger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn interpolate(template: &str, args: &[(&str, String)]) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("%{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find('}') {
+            Some(end) => {
+                let name = &after[..end];
+                match args.iter().find(|(arg, _)| *arg == name) {
+                    Some((_, value)) => output.push_str(value),
+                    None => {
+                        output.push_str("<missing:");
+                        output.push_str(name);
+                        output.push('>');
+                    }
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                output.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
     }
+    output.push_str(rest);
+    output
 }
 
 #[macro_export]
 macro_rules! tz {
     ($tz_str:expr) => {{
-        use std::str::FromStr;
-        chrono_tz::Tz::from_str($tz_str).unwrap_or_else(|_| panic!("TimeZoneError: Invalid time zone: {}", $tz_str))
+        $crate::builder::parse_timezone($tz_str).unwrap_or_else(|err| panic!("TimeZoneError: {}", err))
     }};
 }
 
 #[macro_export]
 macro_rules! init_logger {
-    ( 
-        min_level = $level:expr,
-        language = $lang:expr,
-        timezone = $tz_str:expr $(,)?
-    ) => {
-        {
-            let mut logger = $crate::logger::LOGGER.lock().unwrap();
-            logger.set_min_level($level);
-            logger.set_language($lang);
-            let tz = $crate::tz!($tz_str);
-            logger.set_timezone(tz);
-        }
+    ($($name:ident = $value:expr),* $(,)?) => {{
+        let builder = $crate::builder::LoggerBuilder::new();
+        $(let builder = $crate::__init_arg!(builder, $name, $value);)*
+        builder.install()
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __init_arg {
+    ($builder:ident, min_level, $value:expr) => {
+        $builder.level($value)
+    };
+    ($builder:ident, language, $value:expr) => {
+        $builder.language($value)
+    };
+    ($builder:ident, fallbacks, $value:expr) => {
+        $builder.fallbacks($value)
+    };
+    ($builder:ident, timezone, $value:expr) => {
+        $builder.timezone($value)
+    };
+    ($builder:ident, theme, $value:expr) => {
+        $builder.theme($value)
     };
 }
 
+#[cfg(feature = "verify-keys")]
+#[doc(hidden)]
 #[macro_export]
-macro_rules! error {
-    ($key:expr) => {{
-        $crate::logger::LOGGER.lock().unwrap().log(
-            $crate::logger::LogLevel::Error,
-            $key
-        );
+macro_rules! __key {
+    ($key:expr) => {
+        $crate::__verify_key!($crate; $key)
+    };
+}
+
+#[cfg(not(feature = "verify-keys"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __key {
+    ($key:expr) => {
+        $key
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __fields {
+    (@[$($out:expr),*]) => {
+        [$($out),*]
+    };
+    (@[$($out:expr),*] $name:ident = ?$value:expr $(, $($rest:tt)*)?) => {
+        $crate::__fields!(@[$($out,)* (stringify!($name), $crate::field::FieldValue::Str(format!("{:?}", $value)))] $($($rest)*)?)
+    };
+    (@[$($out:expr),*] $name:ident = %$value:expr $(, $($rest:tt)*)?) => {
+        $crate::__fields!(@[$($out,)* (stringify!($name), $crate::field::FieldValue::Str(format!("{}", $value)))] $($($rest)*)?)
+    };
+    (@[$($out:expr),*] $name:ident = $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__fields!(@[$($out,)* (stringify!($name), {
+            #[allow(unused_imports)]
+            use $crate::field::{Displayed as _, Typed as _};
+            (&$crate::field::Field(&$value)).field_value()
+        })] $($($rest)*)?)
+    };
+}
+
+#[macro_export]
+macro_rules! fatal {
+    (logger: $logger:expr, $key:expr $(, $name:ident = $value:expr)* $(,)? $(; $($fields:tt)*)?) => {{
+        let args: &[(&str, ::std::string::String)] = &[$((stringify!($name), ::std::string::ToString::to_string(&$value))),*];
+        $logger.fatal(module_path!(), $crate::__key!($key), args, &$crate::__fields!(@[] $($($fields)*)?))
+    }};
+    ($key:expr $(, $name:ident = $value:expr)* $(,)? $(; $($fields:tt)*)?) => {{
+        let args: &[(&str, ::std::string::String)] = &[$((stringify!($name), ::std::string::ToString::to_string(&$value))),*];
+        let fields: &[(&str, $crate::field::FieldValue)] = &$crate::__fields!(@[] $($($fields)*)?);
+        $crate::logger::LOGGER.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).fatal(module_path!(), $crate::__key!($key), args, fields)
     }};
 }
 
+#[macro_export]
+macro_rules! error {
+    (logger: $logger:expr, $($rest:tt)*) => {
+        $crate::log!(logger: $logger, $crate::logger::LogLevel::Error, $($rest)*)
+    };
+    ($($rest:tt)*) => {
+        $crate::log!($crate::logger::LogLevel::Error, $($rest)*)
+    };
+}
+
 #[macro_export]
 macro_rules! warn {
-    ($key:expr) => {{
-        $crate::logger::LOGGER.lock().unwrap().log(
-            $crate::logger::LogLevel::Warning,
-            $key
-        );
-    }};
+    (logger: $logger:expr, $($rest:tt)*) => {
+        $crate::log!(logger: $logger, $crate::logger::LogLevel::Warning, $($rest)*)
+    };
+    ($($rest:tt)*) => {
+        $crate::log!($crate::logger::LogLevel::Warning, $($rest)*)
+    };
 }
 
 #[macro_export]
 macro_rules! info {
-    ($key:expr) => {{
-        $crate::logger::LOGGER.lock().unwrap().log(
-            $crate::logger::LogLevel::Info,
-            $key
-        );
-    }};
+    (logger: $logger:expr, $($rest:tt)*) => {
+        $crate::log!(logger: $logger, $crate::logger::LogLevel::Info, $($rest)*)
+    };
+    ($($rest:tt)*) => {
+        $crate::log!($crate::logger::LogLevel::Info, $($rest)*)
+    };
 }
 
 #[macro_export]
 macro_rules! debug {
-    ($key:expr) => {{
-        $crate::logger::LOGGER.lock().unwrap().log(
-            $crate::logger::LogLevel::Debug,
-            $key
-        );
+    (logger: $logger:expr, $($rest:tt)*) => {
+        $crate::log!(logger: $logger, $crate::logger::LogLevel::Debug, $($rest)*)
+    };
+    ($($rest:tt)*) => {
+        $crate::log!($crate::logger::LogLevel::Debug, $($rest)*)
+    };
+}
+
+#[macro_export]
+macro_rules! trace {
+    (logger: $logger:expr, $($rest:tt)*) => {
+        $crate::log!(logger: $logger, $crate::logger::LogLevel::Trace, $($rest)*)
+    };
+    ($($rest:tt)*) => {
+        $crate::log!($crate::logger::LogLevel::Trace, $($rest)*)
+    };
+}
+
+#[macro_export]
+macro_rules! log {
+    (logger: $logger:expr, $level:expr, $key:expr $(, $name:ident = $value:expr)* $(,)? $(; $($fields:tt)*)?) => {{
+        let logger = &$logger;
+        let level = $level;
+        if logger.enabled(module_path!(), level) {
+            let args: &[(&str, ::std::string::String)] = &[$((stringify!($name), ::std::string::ToString::to_string(&$value))),*];
+            logger.log(level, module_path!(), $crate::__key!($key), args, &$crate::__fields!(@[] $($($fields)*)?));
+        }
+    }};
+    ($level:expr, $key:expr $(, $name:ident = $value:expr)* $(,)? $(; $($fields:tt)*)?) => {{
+        // The guard is released before arguments and fields are formatted, so their `Display` impls may log
+        // too, and before a missing translation panics, so the panic does not poison the logger.
+        let level = $level;
+        if $crate::logger::LOGGER.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).enabled(module_path!(), level) {
+            let args: &[(&str, ::std::string::String)] = &[$((stringify!($name), ::std::string::ToString::to_string(&$value))),*];
+            let fields: &[(&str, $crate::field::FieldValue)] = &$crate::__fields!(@[] $($($fields)*)?);
+            let logged = $crate::logger::LOGGER.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).try_log(level, module_path!(), $crate::__key!($key), args, fields);
+            if let Err(message) = logged {
+                panic!("{}", message);
+            }
+        }
     }};
+}
+
+/// Builds a [`Catalog`](crate::catalog::Catalog) from the crate's `locales/` (or `locale/`) files,
+/// embedded at compile time. These are the files `verify-keys` checks literal keys against; log calls
+/// with a checked key register them on first use, so this is only needed to resolve computed keys
+/// before then: `vapor::include_locales!()?.register()?;`
+#[cfg(feature = "verify-keys")]
+#[macro_export]
+macro_rules! include_locales {
+    () => {
+        $crate::catalog::Catalog::from_files(&$crate::__locale_files!())
+    };
+}
+
+/// Adds fields to every record logged on this thread until the returned guard drops:
+/// `let _guard = context!(request_id = id, user = ?user);`
+#[macro_export]
+macro_rules! context {
+    ($($fields:tt)*) => {
+        $crate::context::push(&$crate::__fields!(@[] $($fields)*))
+    };
 }
\ No newline at end of file
@@ -0,0 +1,104 @@
+use crate::config::Config;
+use crate::error::VaporError;
+use crate::logger::{LOGGER, LogLevel, Logger};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime};
+
+/// Stops watching when dropped.
+pub struct ReloadGuard {
+    stop: Option<mpsc::Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+struct Watcher {
+    path: PathBuf,
+    config: Config,
+    stamp: Option<(SystemTime, u64)>,
+}
+
+/// Loads `path` into the global logger, then polls it every `interval` and applies changes.
+/// An update that fails to load is reported and the previous configuration stays in effect.
+pub fn watch(path: impl AsRef<Path>, interval: Duration) -> Result<ReloadGuard, VaporError> {
+    let path = path.as_ref().to_path_buf();
+    let stamp = stamp(&path);
+    let config = Config::load(&path)?;
+    config.apply(&mut LOGGER.lock().unwrap_or_else(|poisoned| poisoned.into_inner()))?;
+
+    let mut watcher = Watcher { path, config, stamp };
+    let (stop, stopped) = mpsc::channel();
+    let handle = thread::Builder::new()
+        .name("vapor-reload".to_string())
+        .spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+                watcher.poll();
+            }
+        })
+        .expect("failed to spawn vapor reload thread");
+
+    Ok(ReloadGuard {
+        stop: Some(stop),
+        handle: Some(handle),
+    })
+}
+
+impl Watcher {
+    fn poll(&mut self) {
+        let stamp = stamp(&self.path);
+        if stamp == self.stamp {
+            return;
+        }
+        self.stamp = stamp;
+
+        let path = self.path.display().to_string();
+        let loaded = Config::load(&self.path);
+        let mut logger = LOGGER.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let config = match loaded {
+            Ok(config) => config,
+            Err(err) => {
+                let args = [("path", path), ("error", err.to_string())];
+                report(&logger, LogLevel::Warning, "config.rejected", &args);
+                return;
+            }
+        };
+
+        let changes = config.changes(&self.config);
+        if changes.is_empty() {
+            return;
+        }
+        if let Err(err) = config.apply(&mut logger) {
+            let args = [("path", path), ("error", err.to_string())];
+            report(&logger, LogLevel::Warning, "config.rejected", &args);
+            return;
+        }
+        #[cfg(feature = "log")]
+        crate::log_bridge::update_max_level(&logger);
+        self.config = config;
+        let args = [("path", path), ("changes", changes.join(", "))];
+        report(&logger, LogLevel::Info, "config.reloaded", &args);
+    }
+}
+
+/// Logs on the watcher thread while holding the global logger, so a missing translation under
+/// `MissingPolicy::Panic` is written as a plain message instead of panicking, as `Logger::fatal` does.
+fn report(logger: &Logger, level: LogLevel, key: &str, args: &[(&str, String)]) {
+    if let Err(message) = logger.try_log(level, module_path!(), key, args, &[]) {
+        logger.log_message(level, module_path!(), &message, &[]);
+    }
+}
+
+fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+impl Drop for ReloadGuard {
+    fn drop(&mut self) {
+        self.stop.take();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use crate::field::FieldValue;
+use crate::logger::{LogLevel, Record};
+use crate::terminal::{ColorSupport, Stream};
+use chrono::SecondsFormat;
+use serde_json::{Map, Value};
+use serde::Deserialize;
+use std::io::{self, Write};
+
+pub trait Sink: Send + Sync {
+    fn write(&self, record: &Record);
+
+    fn flush(&self) {}
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    #[default]
+    Text,
+    /// One JSON object per line. Integer, float and boolean fields are written unquoted;
+    /// everything else, including `%` and `?` fields, is a string.
+    Json,
+}
+
+impl Format {
+    pub(crate) fn render(self, record: &Record) -> String {
+        match self {
+            Format::Text => format_plain(record),
+            Format::Json => format_json(record),
+        }
+    }
+}
+
+pub struct ConsoleSink {
+    format: Format,
+    stream: Stream,
+    stderr_from: Option<LogLevel>,
+    routes: Vec<(LogLevel, Stream)>,
+    stdout_color: ColorSupport,
+    stderr_color: ColorSupport,
+}
+
+impl ConsoleSink {
+    pub fn new() -> Self {
+        Self {
+            format: Format::Text,
+            stream: Stream::Stdout,
+            stderr_from: None,
+            routes: Vec::new(),
+            stdout_color: ColorSupport::detect(Stream::Stdout),
+            stderr_color: ColorSupport::detect(Stream::Stderr),
+        }
+    }
+
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn color(mut self, color: ColorSupport) -> Self {
+        self.stdout_color = color;
+        self.stderr_color = color;
+        self
+    }
+
+    pub fn stream(mut self, stream: Stream) -> Self {
+        self.stream = stream;
+        self
+    }
+
+    pub fn stderr_from(mut self, level: LogLevel) -> Self {
+        self.stderr_from = Some(level);
+        self
+    }
+
+    pub fn route(mut self, level: LogLevel, stream: Stream) -> Self {
+        self.routes.retain(|(existing, _)| *existing != level);
+        self.routes.push((level, stream));
+        self
+    }
+
+    pub fn stream_for(&self, level: LogLevel) -> Stream {
+        if let Some((_, stream)) = self.routes.iter().find(|(routed, _)| *routed == level) {
+            return *stream;
+        }
+        match self.stderr_from {
+            Some(threshold) if level >= threshold => Stream::Stderr,
+            _ => self.stream,
+        }
+    }
+}
+
+impl Default for ConsoleSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sink for ConsoleSink {
+    fn write(&self, record: &Record) {
+        let stream = self.stream_for(record.level);
+        let line = match self.format {
+            Format::Json => format_json(record),
+            Format::Text => {
+                let color = match stream {
+                    Stream::Stdout => self.stdout_color,
+                    Stream::Stderr => self.stderr_color,
+                };
+                let theme = &record.theme;
+                let style = theme.level(record.level);
+                format!(
+                    "{} {} {}{}",
+                    theme.timestamp.paint(&record.timestamp, color),
+                    style.badge.paint(&format!("[{}]", record.label), color),
+                    style.message.paint(&record.message, color),
+                    format_fields(&record.fields)
+                )
+            }
+        };
+
+        let _ = match stream {
+            Stream::Stdout => writeln!(io::stdout().lock(), "{}", line),
+            Stream::Stderr => writeln!(io::stderr().lock(), "{}", line),
+        };
+    }
+
+    fn flush(&self) {
+        let _ = io::stdout().flush();
+        let _ = io::stderr().flush();
+    }
+}
+
+/// Passes on only the records at or above `level`.
+pub struct LeveledSink {
+    level: LogLevel,
+    inner: Box<dyn Sink>,
+}
+
+impl LeveledSink {
+    pub fn new(level: LogLevel, sink: impl Sink + 'static) -> Self {
+        Self {
+            level,
+            inner: Box::new(sink),
+        }
+    }
+}
+
+impl Sink for LeveledSink {
+    fn write(&self, record: &Record) {
+        if record.level >= self.level {
+            self.inner.write(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+pub(crate) fn format_plain(record: &Record) -> String {
+    format!(
+        "{} [{}] {}{}",
+        record.timestamp,
+        record.label,
+        record.message,
+        format_fields(&record.fields)
+    )
+}
+
+pub(crate) fn format_fields(fields: &[(String, FieldValue)]) -> String {
+    let mut output = String::new();
+    for (key, value) in fields {
+        let value = value.to_string();
+        if value.is_empty() || value.contains(|c: char| c.is_whitespace() || c == '=' || c == '"') {
+            output.push_str(&format!(" {}={:?}", key, value));
+        } else {
+            output.push_str(&format!(" {}={}", key, value));
+        }
+    }
+    output
+}
+
+pub(crate) fn format_json(record: &Record) -> String {
+    let mut object = Map::new();
+    object.insert("timestamp".to_string(), record.time.to_rfc3339_opts(SecondsFormat::Millis, false).into());
+    object.insert("level".to_string(), record.level.as_str().into());
+    object.insert("target".to_string(), record.target.clone().into());
+    object.insert("key".to_string(), record.key.clone().into());
+    object.insert("message".to_string(), record.message.clone().into());
+    object.insert("lang".to_string(), record.language.clone().into());
+    if record.locale != record.language {
+        object.insert("locale".to_string(), record.locale.clone().into());
+    }
+    if !record.fields.is_empty() {
+        let fields = record
+            .fields
+            .iter()
+            .map(|(key, value)| (key.clone(), value.to_json()))
+            .collect();
+        object.insert("fields".to_string(), Value::Object(fields));
+    }
+    Value::Object(object).to_string()
+}
@@ -0,0 +1,139 @@
+use crate::theme::Color;
+use serde::Deserialize;
+use std::env;
+use std::io::{self, IsTerminal};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorSupport {
+    None,
+    Ansi16,
+    Ansi256,
+    TrueColor,
+}
+
+impl Stream {
+    pub fn is_terminal(self) -> bool {
+        match self {
+            Stream::Stdout => io::stdout().is_terminal(),
+            Stream::Stderr => io::stderr().is_terminal(),
+        }
+    }
+}
+
+impl ColorSupport {
+    /// Honours `CLICOLOR_FORCE`, `NO_COLOR`, `CLICOLOR`, `TERM` and `COLORTERM`, in that order.
+    pub fn detect(stream: Stream) -> Self {
+        let forced = env_flag("CLICOLOR_FORCE") == Some(true);
+        if !forced {
+            if env_set("NO_COLOR") || env_flag("CLICOLOR") == Some(false) || !stream.is_terminal() {
+                return ColorSupport::None;
+            }
+            if env::var("TERM").is_ok_and(|term| term == "dumb") {
+                return ColorSupport::None;
+            }
+        }
+        Self::from_terminal_env()
+    }
+
+    fn from_terminal_env() -> Self {
+        let colorterm = env::var("COLORTERM").unwrap_or_default().to_ascii_lowercase();
+        if colorterm == "truecolor" || colorterm == "24bit" || env_set("WT_SESSION") {
+            return ColorSupport::TrueColor;
+        }
+        let term = env::var("TERM").unwrap_or_default();
+        if term.contains("256color") {
+            ColorSupport::Ansi256
+        } else {
+            ColorSupport::Ansi16
+        }
+    }
+
+    pub(crate) fn foreground(self, Color(r, g, b): Color) -> Option<String> {
+        match self {
+            ColorSupport::None => None,
+            ColorSupport::TrueColor => Some(format!("38;2;{};{};{}", r, g, b)),
+            ColorSupport::Ansi256 => Some(format!("38;5;{}", nearest_256(r, g, b))),
+            ColorSupport::Ansi16 => Some(nearest_16(r, g, b).to_string()),
+        }
+    }
+}
+
+fn env_set(name: &str) -> bool {
+    env::var_os(name).is_some_and(|value| !value.is_empty())
+}
+
+fn env_flag(name: &str) -> Option<bool> {
+    env::var(name).ok().map(|value| value != "0")
+}
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let dr = r1 as i32 - r2 as i32;
+    let dg = g1 as i32 - g2 as i32;
+    let db = b1 as i32 - b2 as i32;
+    (dr * dr + dg * dg + db * db) as u32
+}
+
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn nearest_cube_level(value: u8) -> usize {
+    CUBE_LEVELS
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, level)| (value as i32 - **level as i32).abs())
+        .map(|(index, _)| index)
+        .unwrap()
+}
+
+fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+    let (ri, gi, bi) = (nearest_cube_level(r), nearest_cube_level(g), nearest_cube_level(b));
+    let cube = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_step = (average.saturating_sub(8) / 10).min(23) as u8;
+    let gray_value = 8 + gray_step * 10;
+    let gray = (gray_value, gray_value, gray_value);
+    let gray_index = 232 + gray_step as usize;
+
+    if distance((r, g, b), gray) < distance((r, g, b), cube) {
+        gray_index as u8
+    } else {
+        cube_index as u8
+    }
+}
+
+const ANSI_16: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+fn nearest_16(r: u8, g: u8, b: u8) -> u8 {
+    let index = ANSI_16
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, color)| distance((r, g, b), **color))
+        .map(|(index, _)| index as u8)
+        .unwrap();
+    if index < 8 { 30 + index } else { 90 + index - 8 }
+}
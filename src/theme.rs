@@ -0,0 +1,268 @@
+use crate::logger::LogLevel;
+use crate::terminal::ColorSupport;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "ColorRepr")]
+pub struct Color(pub u8, pub u8, pub u8);
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ColorRepr {
+    Hex(String),
+    Rgb([u8; 3]),
+}
+
+impl TryFrom<ColorRepr> for Color {
+    type Error = String;
+
+    fn try_from(repr: ColorRepr) -> Result<Self, Self::Error> {
+        match repr {
+            ColorRepr::Rgb([r, g, b]) => Ok(Color(r, g, b)),
+            ColorRepr::Hex(hex) => {
+                let invalid = || format!("invalid color {:?}, expected \"#rrggbb\" or [r, g, b]", hex);
+                let digits = hex.strip_prefix('#').unwrap_or(&hex);
+                if digits.len() != 6 {
+                    return Err(invalid());
+                }
+                let channel = |i: usize| {
+                    digits
+                        .get(i..i + 2)
+                        .and_then(|c| u8::from_str_radix(c, 16).ok())
+                        .ok_or_else(invalid)
+                };
+                Ok(Color(channel(0)?, channel(2)?, channel(4)?))
+            }
+        }
+    }
+}
+
+impl From<(u8, u8, u8)> for Color {
+    fn from((r, g, b): (u8, u8, u8)) -> Self {
+        Color(r, g, b)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Style {
+    pub color: Option<Color>,
+    pub bold: bool,
+    pub italic: bool,
+    pub dim: bool,
+}
+
+impl Style {
+    pub const fn plain() -> Self {
+        Self {
+            color: None,
+            bold: false,
+            italic: false,
+            dim: false,
+        }
+    }
+
+    pub const fn color(r: u8, g: u8, b: u8) -> Self {
+        Self {
+            color: Some(Color(r, g, b)),
+            ..Self::plain()
+        }
+    }
+
+    pub const fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    pub const fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    pub const fn dim(mut self) -> Self {
+        self.dim = true;
+        self
+    }
+
+    pub(crate) fn paint(&self, text: &str, support: ColorSupport) -> String {
+        if support == ColorSupport::None {
+            return text.to_string();
+        }
+
+        let mut codes = Vec::new();
+        if self.bold {
+            codes.push("1".to_string());
+        }
+        if self.dim {
+            codes.push("2".to_string());
+        }
+        if self.italic {
+            codes.push("3".to_string());
+        }
+        if let Some(code) = self.color.and_then(|color| support.foreground(color)) {
+            codes.push(code);
+        }
+
+        if codes.is_empty() {
+            text.to_string()
+        } else {
+            format!("\x1b[{}m{}\x1b[0m", codes.join(";"), text)
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LevelStyle {
+    pub badge: Style,
+    pub message: Style,
+}
+
+impl LevelStyle {
+    const fn new(badge: Style, message: Style) -> Self {
+        Self { badge, message }
+    }
+
+    const fn same(style: Style) -> Self {
+        Self::new(style, style)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Theme {
+    pub timestamp: Style,
+    pub trace: LevelStyle,
+    pub debug: LevelStyle,
+    pub info: LevelStyle,
+    pub warning: LevelStyle,
+    pub error: LevelStyle,
+    pub fatal: LevelStyle,
+    pub custom: HashMap<String, LevelStyle>,
+}
+
+#[derive(Debug)]
+pub enum ThemeError {
+    Io(PathBuf, io::Error),
+    Parse(PathBuf, String),
+    UnsupportedFormat(PathBuf),
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeError::Io(path, err) => write!(f, "cannot read theme {}: {}", path.display(), err),
+            ThemeError::Parse(path, err) => write!(f, "invalid theme {}: {}", path.display(), err),
+            ThemeError::UnsupportedFormat(path) => {
+                write!(f, "unsupported theme file {}, expected .yml, .yaml or .toml", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::vapor()
+    }
+}
+
+impl Theme {
+    pub fn vapor() -> Self {
+        Self {
+            timestamp: Style::plain(),
+            trace: LevelStyle::same(Style::color(56, 66, 72)),
+            debug: LevelStyle::same(Style::color(82, 97, 107)),
+            info: LevelStyle::new(Style::color(48, 227, 202), Style::color(255, 255, 255)),
+            warning: LevelStyle::same(Style::color(249, 237, 105)),
+            error: LevelStyle::same(Style::color(255, 46, 99)),
+            fatal: LevelStyle::same(Style::color(199, 0, 57)),
+            custom: HashMap::new(),
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Self {
+            timestamp: Style::color(255, 255, 255),
+            trace: LevelStyle::same(Style::color(170, 170, 170)),
+            debug: LevelStyle::same(Style::color(210, 210, 210)),
+            info: LevelStyle::new(Style::color(0, 255, 255).bold(), Style::color(255, 255, 255)),
+            warning: LevelStyle::new(Style::color(255, 255, 0).bold(), Style::color(255, 255, 0)),
+            error: LevelStyle::new(Style::color(255, 64, 64).bold(), Style::color(255, 64, 64)),
+            fatal: LevelStyle::same(Style::color(255, 0, 0).bold()),
+            custom: HashMap::new(),
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            timestamp: Style::color(110, 110, 110),
+            trace: LevelStyle::same(Style::color(150, 150, 150)),
+            debug: LevelStyle::same(Style::color(100, 110, 120)),
+            info: LevelStyle::new(Style::color(0, 128, 128), Style::color(30, 30, 30)),
+            warning: LevelStyle::same(Style::color(176, 112, 0)),
+            error: LevelStyle::same(Style::color(200, 0, 40)),
+            fatal: LevelStyle::same(Style::color(150, 0, 0).bold()),
+            custom: HashMap::new(),
+        }
+    }
+
+    pub fn monochrome() -> Self {
+        Self {
+            timestamp: Style::plain().dim(),
+            trace: LevelStyle::same(Style::plain().dim().italic()),
+            debug: LevelStyle::same(Style::plain().dim()),
+            info: LevelStyle::new(Style::plain().bold(), Style::plain()),
+            warning: LevelStyle::new(Style::plain().bold(), Style::plain().italic()),
+            error: LevelStyle::same(Style::plain().bold()),
+            fatal: LevelStyle::same(Style::plain().bold().italic()),
+            custom: HashMap::new(),
+        }
+    }
+
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().replace('_', "-").as_str() {
+            "vapor" | "default" => Some(Self::vapor()),
+            "high-contrast" => Some(Self::high_contrast()),
+            "light" => Some(Self::light()),
+            "monochrome" => Some(Self::monochrome()),
+            _ => None,
+        }
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ThemeError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|err| ThemeError::Io(path.to_path_buf(), err))?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("yml") | Some("yaml") => {
+                serde_yaml::from_str(&content).map_err(|err| ThemeError::Parse(path.to_path_buf(), err.to_string()))
+            }
+            Some("toml") => toml::from_str(&content).map_err(|err| ThemeError::Parse(path.to_path_buf(), err.to_string())),
+            _ => Err(ThemeError::UnsupportedFormat(path.to_path_buf())),
+        }
+    }
+
+    pub fn level(&self, level: LogLevel) -> LevelStyle {
+        match level {
+            LogLevel::Trace => self.trace,
+            LogLevel::Debug => self.debug,
+            LogLevel::Info => self.info,
+            LogLevel::Warning => self.warning,
+            LogLevel::Error => self.error,
+            LogLevel::Fatal => self.fatal,
+            LogLevel::Custom(custom) => {
+                let info = custom.info();
+                self.custom
+                    .get(info.name)
+                    .copied()
+                    .unwrap_or_else(|| LevelStyle::same(Style::color(info.color.0, info.color.1, info.color.2)))
+            }
+        }
+    }
+}
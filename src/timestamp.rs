@@ -0,0 +1,64 @@
+use chrono::{DateTime, FixedOffset, SecondsFormat};
+use serde::Deserialize;
+use std::fmt::Write;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum TimeFormat {
+    #[default]
+    Vapor,
+    Rfc3339,
+    Iso8601Basic,
+    EpochMillis,
+    /// A chrono strftime string. Precision does not apply here; use `%.3f` and friends instead.
+    Custom(String),
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Precision {
+    #[default]
+    Seconds,
+    Millis,
+    Micros,
+    Nanos,
+}
+
+impl Precision {
+    fn fraction(self) -> &'static str {
+        match self {
+            Precision::Seconds => "",
+            Precision::Millis => "%.3f",
+            Precision::Micros => "%.6f",
+            Precision::Nanos => "%.9f",
+        }
+    }
+
+    fn seconds_format(self) -> SecondsFormat {
+        match self {
+            Precision::Seconds => SecondsFormat::Secs,
+            Precision::Millis => SecondsFormat::Millis,
+            Precision::Micros => SecondsFormat::Micros,
+            Precision::Nanos => SecondsFormat::Nanos,
+        }
+    }
+}
+
+impl TimeFormat {
+    pub fn format(&self, time: &DateTime<FixedOffset>, precision: Precision) -> String {
+        match self {
+            TimeFormat::Vapor => strftime(time, &format!("%Y/%-m/%-d %H:%M:%S{}", precision.fraction())),
+            TimeFormat::Rfc3339 => time.to_rfc3339_opts(precision.seconds_format(), false),
+            TimeFormat::Iso8601Basic => strftime(time, &format!("%Y%m%dT%H%M%S{}%z", precision.fraction())),
+            TimeFormat::EpochMillis => time.timestamp_millis().to_string(),
+            TimeFormat::Custom(pattern) => strftime(time, pattern),
+        }
+    }
+}
+
+fn strftime(time: &DateTime<FixedOffset>, pattern: &str) -> String {
+    let mut output = String::new();
+    match write!(output, "{}", time.format(pattern)) {
+        Ok(()) => output,
+        Err(_) => time.to_rfc3339(),
+    }
+}
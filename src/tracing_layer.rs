@@ -0,0 +1,142 @@
+use crate::field::FieldValue;
+use crate::logger::{LOGGER, LogLevel};
+use std::fmt;
+use tracing_core::field::{Field, Visit};
+use tracing_core::span::{Attributes, Id, Record};
+use tracing_core::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+pub struct VaporLayer;
+
+impl VaporLayer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for VaporLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<&Level> for LogLevel {
+    fn from(level: &Level) -> Self {
+        match *level {
+            Level::ERROR => LogLevel::Error,
+            Level::WARN => LogLevel::Warning,
+            Level::INFO => LogLevel::Info,
+            Level::DEBUG => LogLevel::Debug,
+            _ => LogLevel::Trace,
+        }
+    }
+}
+
+struct SpanFields(Vec<(&'static str, FieldValue)>);
+
+#[derive(Default)]
+struct FieldVisitor {
+    message: Option<String>,
+    fields: Vec<(&'static str, FieldValue)>,
+}
+
+impl FieldVisitor {
+    fn record(&mut self, field: &Field, value: FieldValue) {
+        if field.name() == "message" {
+            self.message = Some(value.to_string());
+        } else {
+            self.fields.push((field.name(), value));
+        }
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.record(field, value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.record(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.record(field, value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record(field, FieldValue::Str(format!("{:?}", value)));
+    }
+}
+
+impl<S> Layer<S> for VaporLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        attrs.record(&mut visitor);
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanFields(visitor.fields));
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        values.record(&mut visitor);
+        if let Some(span) = ctx.span(id)
+            && let Some(fields) = span.extensions_mut().get_mut::<SpanFields>()
+        {
+            fields.0.extend(visitor.fields);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let level = LogLevel::from(metadata.level());
+        // Field values are formatted without holding the logger lock, so their `Debug` impls may log too.
+        if !LOGGER.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).enabled(metadata.target(), level) {
+            return;
+        }
+
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        let message = visitor.message.unwrap_or_default();
+        let mut fields = visitor.fields;
+
+        if let Some(scope) = ctx.event_scope(event) {
+            let context = scope
+                .from_root()
+                .map(|span| {
+                    let extensions = span.extensions();
+                    match extensions.get::<SpanFields>() {
+                        Some(SpanFields(span_fields)) if !span_fields.is_empty() => {
+                            let span_fields: Vec<String> =
+                                span_fields.iter().map(|(key, value)| format!("{}={}", key, value)).collect();
+                            format!("{}{{{}}}", span.name(), span_fields.join(" "))
+                        }
+                        _ => span.name().to_string(),
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(":");
+            fields.push(("span", FieldValue::Str(context)));
+        }
+
+        let args: Vec<(&str, String)> = fields.iter().map(|(key, value)| (*key, value.to_string())).collect();
+        let logger = LOGGER.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if logger.has_key(&message) {
+            logger.log(level, metadata.target(), &message, &args, &fields);
+        } else {
+            logger.log_message(level, metadata.target(), &message, &fields);
+        }
+    }
+}
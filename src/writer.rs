@@ -0,0 +1,201 @@
+use crate::logger::Record;
+use crate::sink::Sink;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+
+pub(crate) type Sinks = Arc<Vec<Arc<dyn Sink>>>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Overflow {
+    Block,
+    DropNewest,
+    DropOldest,
+}
+
+enum Job {
+    Write(Box<Record>, Sinks),
+    Flush(Sinks, mpsc::Sender<()>),
+}
+
+struct State {
+    jobs: VecDeque<Job>,
+    closed: bool,
+}
+
+pub(crate) struct Queue {
+    state: Mutex<State>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    capacity: usize,
+    overflow: Overflow,
+    dropped: AtomicU64,
+}
+
+// Closes the queue when the writer thread stops, including when a sink panics, so callers
+// blocked on a full queue or waiting for a flush are released.
+struct Stopped<'a>(&'a Queue);
+
+pub struct WriterGuard {
+    queue: Arc<Queue>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Queue {
+    pub(crate) fn spawn(capacity: usize, overflow: Overflow) -> (Arc<Queue>, WriterGuard) {
+        let queue = Arc::new(Queue {
+            state: Mutex::new(State {
+                jobs: VecDeque::with_capacity(capacity),
+                closed: false,
+            }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            capacity: capacity.max(1),
+            overflow,
+            dropped: AtomicU64::new(0),
+        });
+
+        let worker = Arc::clone(&queue);
+        let handle = thread::Builder::new()
+            .name("vapor-writer".to_string())
+            .spawn(move || worker.run())
+            .expect("failed to spawn vapor writer thread");
+
+        let guard = WriterGuard {
+            queue: Arc::clone(&queue),
+            handle: Some(handle),
+        };
+        (queue, guard)
+    }
+
+    pub(crate) fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    // Hands the record back when the writer thread has already shut down.
+    pub(crate) fn push(&self, record: Record, sinks: Sinks) -> Option<Record> {
+        let mut state = self.state.lock().unwrap();
+        if state.closed {
+            return Some(record);
+        }
+
+        while state.jobs.len() >= self.capacity {
+            match self.overflow {
+                Overflow::Block => {
+                    state = self.not_full.wait(state).unwrap();
+                    if state.closed {
+                        return Some(record);
+                    }
+                }
+                Overflow::DropNewest => {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    return None;
+                }
+                Overflow::DropOldest => {
+                    match state.jobs.iter().position(|job| matches!(job, Job::Write(..))) {
+                        Some(index) => {
+                            state.jobs.remove(index);
+                            self.dropped.fetch_add(1, Ordering::Relaxed);
+                        }
+                        None => {
+                            self.dropped.fetch_add(1, Ordering::Relaxed);
+                            return None;
+                        }
+                    }
+                }
+            }
+        }
+
+        state.jobs.push_back(Job::Write(Box::new(record), sinks));
+        self.not_empty.notify_one();
+        None
+    }
+
+    // Waits until every record queued before this call has been written and the sinks flushed.
+    pub(crate) fn flush(&self, sinks: Sinks) -> bool {
+        let (sender, receiver) = mpsc::channel();
+        {
+            let mut state = self.state.lock().unwrap();
+            if state.closed {
+                return false;
+            }
+            state.jobs.push_back(Job::Flush(sinks, sender));
+            self.not_empty.notify_one();
+        }
+        receiver.recv().is_ok()
+    }
+
+    fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+
+    fn run(&self) {
+        let _stopped = Stopped(self);
+        let mut last_sinks: Option<Sinks> = None;
+        loop {
+            let job = {
+                let mut state = self.state.lock().unwrap();
+                while state.jobs.is_empty() && !state.closed {
+                    state = self.not_empty.wait(state).unwrap();
+                }
+                match state.jobs.pop_front() {
+                    Some(job) => job,
+                    None => break,
+                }
+            };
+            self.not_full.notify_one();
+
+            match job {
+                Job::Write(record, sinks) => {
+                    for sink in sinks.iter() {
+                        sink.write(&record);
+                    }
+                    last_sinks = Some(sinks);
+                }
+                Job::Flush(sinks, done) => {
+                    for sink in sinks.iter() {
+                        sink.flush();
+                    }
+                    let _ = done.send(());
+                }
+            }
+        }
+
+        if let Some(sinks) = last_sinks {
+            for sink in sinks.iter() {
+                sink.flush();
+            }
+        }
+    }
+}
+
+impl Drop for Stopped<'_> {
+    fn drop(&mut self) {
+        let mut state = self.0.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.closed = true;
+        let lost = state.jobs.drain(..).filter(|job| matches!(job, Job::Write(..))).count();
+        self.0.dropped.fetch_add(lost as u64, Ordering::Relaxed);
+        drop(state);
+        self.0.not_empty.notify_all();
+        self.0.not_full.notify_all();
+    }
+}
+
+impl WriterGuard {
+    pub fn dropped(&self) -> u64 {
+        self.queue.dropped()
+    }
+}
+
+impl Drop for WriterGuard {
+    fn drop(&mut self) {
+        self.queue.close();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
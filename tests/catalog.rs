@@ -0,0 +1,110 @@
+use std::{env, fs, process};
+use vapor::catalog::{Catalog, CatalogError, CatalogFormat, Conflict};
+use vapor::locale;
+
+fn conflicts(err: CatalogError) -> Vec<Conflict> {
+    match err {
+        CatalogError::Conflicts(conflicts) => conflicts,
+        err => panic!("expected conflicts, got {}", err),
+    }
+}
+
+fn conflict(locale: &str, key: &str, first: &str, second: &str) -> Conflict {
+    Conflict {
+        locale: locale.to_string(),
+        key: key.to_string(),
+        first: first.to_string(),
+        second: second.to_string(),
+    }
+}
+
+#[test]
+fn reads_embedded_files_by_name() {
+    let files = [
+        ("locales/app.yml", "_version: 2\nembedded:\n  yaml:\n    en: From YAML\n"),
+        ("locales/app.toml", "_version = 2\n\n[embedded.toml]\nen = \"From TOML\"\nja = \"TOML kara\"\n"),
+        ("locales/v1/app.ja.json", "{\"embedded\": {\"json\": \"JSON kara\"}}"),
+    ];
+    let catalog = Catalog::from_files(&files).unwrap();
+    assert_eq!(catalog.len(), 4);
+    catalog.register().unwrap();
+
+    assert_eq!(locale::lookup("en", "embedded.yaml").as_deref(), Some("From YAML"));
+    assert_eq!(locale::lookup("ja", "embedded.toml").as_deref(), Some("TOML kara"));
+    assert_eq!(locale::lookup("ja", "embedded.json").as_deref(), Some("JSON kara"));
+}
+
+#[test]
+fn rejects_unknown_file_types() {
+    let err = Catalog::from_files(&[("locales/app.ini", "")]).unwrap_err();
+    assert!(matches!(err, CatalogError::Parse(source, _) if source == "locales/app.ini"));
+}
+
+#[test]
+fn loads_a_directory_of_v1_and_v2_files() {
+    let dir = env::temp_dir().join(format!("vapor-catalog-{}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("nested")).unwrap();
+    fs::write(dir.join("shop.en.yml"), "shop:\n  open: Open\n").unwrap();
+    fs::write(dir.join("nested/shop.de.json"), "{\"shop\": {\"open\": \"Offen\"}}").unwrap();
+    fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+    let mut catalog = Catalog::new();
+    catalog.add_dir(&dir).unwrap();
+    assert_eq!(catalog.len(), 2);
+    catalog.register().unwrap();
+    assert_eq!(locale::lookup("de", "shop.open").as_deref(), Some("Offen"));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn identical_translations_from_two_sources_agree() {
+    let mut catalog = Catalog::new();
+    catalog.add_str("en", "agree:\n  key: Same\n", CatalogFormat::Yaml).unwrap();
+    catalog.add_str("second", "{\"_version\": 2, \"agree\": {\"key\": {\"en\": \"Same\"}}}", CatalogFormat::Json).unwrap();
+    catalog.register().unwrap();
+    catalog.register().unwrap();
+    assert_eq!(locale::lookup("en", "agree.key").as_deref(), Some("Same"));
+}
+
+#[test]
+fn reports_conflicts_within_a_catalog_and_registers_nothing() {
+    let mut catalog = Catalog::new();
+    catalog.add_str("en", "within:\n  kept: Kept\n  key: First\n", CatalogFormat::Yaml).unwrap();
+    catalog.add_str("other.yml", "_version: 2\nwithin:\n  key:\n    en: Second\n", CatalogFormat::Yaml).unwrap();
+
+    let err = catalog.register().unwrap_err();
+    assert_eq!(conflicts(err), [conflict("en", "within.key", "en", "other.yml")]);
+    assert_eq!(locale::lookup("en", "within.kept"), None);
+}
+
+#[test]
+fn reports_conflicts_with_built_in_translations() {
+    let mut catalog = Catalog::new();
+    catalog.add_str("levels.yml", "_version: 2\ninfo:\n  en: Information\n", CatalogFormat::Yaml).unwrap();
+    let err = catalog.register().unwrap_err();
+    assert_eq!(conflicts(err), [conflict("en", "info", "vapor", "levels.yml")]);
+    assert_eq!(locale::lookup("en", "info").as_deref(), Some("Info"));
+}
+
+#[test]
+fn reports_conflicts_with_earlier_catalogs() {
+    let mut first = Catalog::new();
+    first.add_str("first.yml", "_version: 2\nearlier:\n  key:\n    en: First\n", CatalogFormat::Yaml).unwrap();
+    first.register().unwrap();
+
+    let mut second = Catalog::new();
+    second.add_str("second.yml", "_version: 2\nearlier:\n  key:\n    en: Second\n", CatalogFormat::Yaml).unwrap();
+    let err = second.register().unwrap_err();
+    assert_eq!(conflicts(err), [conflict("en", "earlier.key", "first.yml", "second.yml")]);
+    assert_eq!(locale::lookup("en", "earlier.key").as_deref(), Some("First"));
+}
+
+#[test]
+fn the_macros_crate_checks_keys_against_the_same_translations() {
+    assert_eq!(
+        include_str!("../locale/app.yml"),
+        include_str!("../macros/locale/app.yml"),
+        "copy locale/app.yml to macros/locale/app.yml"
+    );
+}
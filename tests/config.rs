@@ -0,0 +1,165 @@
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::{env, fs, process};
+use vapor::config::{Config, SinkKind};
+use vapor::error::VaporError;
+use vapor::locale::MissingPolicy;
+use vapor::logger::LogLevel;
+use vapor::sink::Format;
+use vapor::timestamp::TimeFormat;
+
+/// `Config::load` reads the `VAPOR_*` variables, so tests that load files run one at a time.
+static ENV: Mutex<()> = Mutex::new(());
+
+fn lock_env() -> MutexGuard<'static, ()> {
+    let guard = ENV.lock().unwrap_or_else(PoisonError::into_inner);
+    for name in ["VAPOR_LEVEL", "VAPOR_LANG", "VAPOR_TZ"] {
+        unsafe { env::remove_var(name) };
+    }
+    guard
+}
+
+fn write(test: &str, name: &str, content: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("vapor-config-{}-{}", test, process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    fs::write(&path, content).unwrap();
+    path
+}
+
+fn position(err: VaporError) -> (Option<usize>, Option<usize>, String) {
+    match err {
+        VaporError::Config {
+            line, column, message, ..
+        } => (line, column, message),
+        err => panic!("expected a config error, got {}", err),
+    }
+}
+
+#[test]
+fn loads_toml_and_yaml_alike() {
+    let _env = lock_env();
+    let toml = write(
+        "alike",
+        "vapor.toml",
+        r#"
+level = "warn"
+language = "zh-cn"
+fallbacks = ["en"]
+missing_translation = "raw-key"
+timezone = "Asia/Shanghai"
+time_format = "%H:%M"
+
+[[sinks]]
+type = "console"
+format = "json"
+
+[[sinks]]
+type = "file"
+path = "app.log"
+max_size = 1024
+"#,
+    );
+    let yaml = write(
+        "alike-yaml",
+        "vapor.yml",
+        r#"
+level: warn
+language: zh-cn
+fallbacks: [en]
+missing_translation: raw-key
+timezone: Asia/Shanghai
+time_format: "%H:%M"
+sinks:
+  - type: console
+    format: json
+  - type: file
+    path: app.log
+    max_size: 1024
+"#,
+    );
+
+    let config = Config::load(&toml).unwrap();
+    assert_eq!(config.level, Some(LogLevel::Warning));
+    assert_eq!(config.language.as_deref(), Some("zh-cn"));
+    assert_eq!(config.fallbacks, ["en"]);
+    assert_eq!(config.missing_translation, Some(MissingPolicy::RawKey));
+    assert_eq!(config.timezone, Some(chrono_tz::Asia::Shanghai));
+    assert_eq!(config.time_format, Some(TimeFormat::Custom("%H:%M".to_string())));
+    assert_eq!(config.sinks.len(), 2);
+    assert_eq!((config.sinks[0].kind, config.sinks[0].format), (SinkKind::Console, Format::Json));
+    assert_eq!((config.sinks[1].kind, config.sinks[1].max_size), (SinkKind::File, Some(1024)));
+
+    let from_yaml = Config::load(&yaml).unwrap();
+    assert!(from_yaml.changes(&config).is_empty());
+}
+
+#[test]
+fn reports_where_a_sink_setting_is_wrong() {
+    let _env = lock_env();
+    let toml = write("toml-position", "vapor.toml", "level = \"info\"\n\n[[sinks]]\ntype = \"file\"\npath = \"app.log\"\nlevel = \"loud\"\n");
+    assert_eq!(
+        position(Config::load(&toml).unwrap_err()),
+        (Some(6), Some(9), "unknown log level: loud".to_string())
+    );
+
+    let yaml = write(
+        "yaml-position",
+        "vapor.yml",
+        "level: info\nsinks:\n  - type: console\n    format: json\n  - type: file\n    path: app.log\n    max_size: 10\n    level: loud\n",
+    );
+    assert_eq!(
+        position(Config::load(&yaml).unwrap_err()),
+        (Some(8), Some(12), "sinks[1].level: unknown log level: loud".to_string())
+    );
+}
+
+#[test]
+fn rejects_settings_for_the_other_sink_type() {
+    let _env = lock_env();
+    let console = write("console-path", "vapor.toml", "[[sinks]]\ntype = \"console\"\npath = \"app.log\"\n");
+    assert_eq!(position(Config::load(&console).unwrap_err()).2, "`path` does not apply to a console sink");
+
+    let file = write("file-stream", "vapor.toml", "[[sinks]]\ntype = \"file\"\npath = \"app.log\"\nstream = \"stderr\"\n");
+    assert_eq!(position(Config::load(&file).unwrap_err()).2, "`stream` does not apply to a file sink");
+
+    let pathless = write("file-pathless", "vapor.toml", "[[sinks]]\ntype = \"file\"\n");
+    assert_eq!(position(Config::load(&pathless).unwrap_err()).2, "missing field `path` for a file sink");
+
+    let unknown = write("unknown-type", "vapor.toml", "[[sinks]]\ntype = \"pipe\"\n");
+    assert_eq!(
+        position(Config::load(&unknown).unwrap_err()),
+        (Some(2), Some(8), "unknown variant `pipe`, expected `console` or `file`".to_string())
+    );
+}
+
+#[test]
+fn resolves_file_sinks_against_the_config_directory() {
+    let _env = lock_env();
+    let path = write("relative", "vapor.toml", "[[sinks]]\ntype = \"file\"\npath = \"logs/app.log\"\n");
+    let config = Config::load(&path).unwrap();
+    config.sinks().unwrap();
+    assert!(path.parent().unwrap().join("logs/app.log").is_file());
+    assert!(!PathBuf::from("logs/app.log").exists());
+}
+
+#[test]
+fn environment_overrides_the_file() {
+    let _env = lock_env();
+    let path = write("env", "vapor.yml", "level: info\nlanguage: en\ntimezone: Etc/UTC\n");
+    unsafe {
+        env::set_var("VAPOR_LEVEL", "trace");
+        env::set_var("VAPOR_LANG", "zh-cn");
+        env::set_var("VAPOR_TZ", "Asia/Tokyo");
+    }
+    let config = Config::load(&path).unwrap();
+    assert_eq!(config.level, Some(LogLevel::Trace));
+    assert_eq!(config.language.as_deref(), Some("zh-cn"));
+    assert_eq!(config.timezone, Some(chrono_tz::Asia::Tokyo));
+
+    unsafe { env::set_var("VAPOR_TZ", "Mars/Base") };
+    let err = Config::load(&path).unwrap_err();
+    assert_eq!(err.to_string(), "$VAPOR_TZ: unknown time zone: Mars/Base");
+    unsafe { env::remove_var("VAPOR_TZ") };
+}
@@ -0,0 +1,36 @@
+use vapor::context;
+use vapor::field::FieldValue;
+
+fn fields() -> Vec<(String, FieldValue)> {
+    context::current()
+}
+
+fn pair(key: &str, value: &str) -> (String, FieldValue) {
+    (key.to_string(), value.into())
+}
+
+#[test]
+fn inner_scopes_win_and_restore_on_drop() {
+    let outer = context::push(&[("request", "1".into()), ("user", "ann".into())]);
+    {
+        let _inner = context::push(&[("user", "bob".into())]);
+        assert_eq!(fields(), [pair("request", "1"), pair("user", "bob")]);
+    }
+    assert_eq!(fields(), [pair("request", "1"), pair("user", "ann")]);
+    drop(outer);
+    assert!(fields().is_empty());
+}
+
+#[test]
+fn guards_may_drop_out_of_order() {
+    let first = context::push(&[("first", "1".into())]);
+    let second = context::push(&[("second", "2".into())]);
+    drop(first);
+    assert_eq!(fields(), [pair("second", "2")]);
+
+    let third = context::push(&[("third", "3".into())]);
+    drop(second);
+    assert_eq!(fields(), [pair("third", "3")]);
+    drop(third);
+    assert!(fields().is_empty());
+}
@@ -0,0 +1,88 @@
+use chrono::DateTime;
+use flate2::read::GzDecoder;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::{env, fs, process};
+use vapor::file::{FileSink, Period};
+use vapor::logger::{LogLevel, Record};
+use vapor::sink::Sink;
+
+fn dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("vapor-file-{}-{}", name, process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn record(time: &str, message: &str) -> Record {
+    Record {
+        time: DateTime::parse_from_rfc3339(time).unwrap(),
+        timestamp: time.to_string(),
+        level: LogLevel::Info,
+        label: "Info".to_string(),
+        target: "file".to_string(),
+        key: "key".to_string(),
+        message: message.to_string(),
+        language: "en".to_string(),
+        locale: "en".to_string(),
+        fields: Vec::new(),
+        theme: Default::default(),
+    }
+}
+
+fn read(path: &Path) -> String {
+    fs::read_to_string(path).unwrap()
+}
+
+#[test]
+fn rotates_by_size_and_keeps_the_newest_files() {
+    let dir = dir("size");
+    let path = dir.join("app.log");
+    let sink = FileSink::new(&path).unwrap().max_size(64).keep(2);
+    for message in ["first", "second", "third", "fourth"] {
+        sink.write(&record("2024-06-01T12:00:00+00:00", message));
+    }
+    sink.flush();
+
+    assert_eq!(read(&path), "2024-06-01T12:00:00+00:00 [Info] fourth\n");
+    assert_eq!(read(&dir.join("app.log.1")), "2024-06-01T12:00:00+00:00 [Info] third\n");
+    assert_eq!(read(&dir.join("app.log.2")), "2024-06-01T12:00:00+00:00 [Info] second\n");
+    assert!(!dir.join("app.log.3").exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn rotates_when_the_period_changes() {
+    let dir = dir("period");
+    let path = dir.join("app.log");
+    let sink = FileSink::new(&path).unwrap().period(Period::Daily);
+    sink.write(&record("2024-06-01T08:00:00+08:00", "morning"));
+    sink.write(&record("2024-06-01T23:59:59+08:00", "night"));
+    sink.write(&record("2024-06-02T00:00:00+08:00", "next day"));
+    sink.flush();
+
+    assert_eq!(
+        read(&dir.join("app.log.1")),
+        "2024-06-01T08:00:00+08:00 [Info] morning\n2024-06-01T23:59:59+08:00 [Info] night\n"
+    );
+    assert_eq!(read(&path), "2024-06-02T00:00:00+08:00 [Info] next day\n");
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn compresses_rotated_files() {
+    let dir = dir("gzip");
+    let path = dir.join("app.log");
+    let sink = FileSink::new(&path).unwrap().max_size(64).compress(true);
+    sink.write(&record("2024-06-01T12:00:00+00:00", "first"));
+    sink.write(&record("2024-06-01T12:00:00+00:00", "second"));
+    sink.write(&record("2024-06-01T12:00:00+00:00", "third"));
+    sink.flush();
+
+    assert!(!dir.join("app.log.1").exists());
+    let mut rotated = String::new();
+    GzDecoder::new(fs::File::open(dir.join("app.log.1.gz")).unwrap()).read_to_string(&mut rotated).unwrap();
+    assert_eq!(rotated, "2024-06-01T12:00:00+00:00 [Info] second\n");
+    assert!(dir.join("app.log.2.gz").exists());
+    assert_eq!(read(&path), "2024-06-01T12:00:00+00:00 [Info] third\n");
+    fs::remove_dir_all(&dir).unwrap();
+}
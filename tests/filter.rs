@@ -0,0 +1,60 @@
+use vapor::filter::Filter;
+use vapor::logger::LogLevel;
+
+#[test]
+fn parses_a_default_and_per_target_directives() {
+    let filter = Filter::parse(" warn, app::db = trace ,hyper=error,", LogLevel::Info).unwrap();
+    assert_eq!(filter.default_level(), LogLevel::Warning);
+    assert_eq!(filter.level_for("app::db"), LogLevel::Trace);
+    assert_eq!(filter.level_for("hyper"), LogLevel::Error);
+    assert_eq!(filter.level_for("app"), LogLevel::Warning);
+}
+
+#[test]
+fn keeps_the_given_default_without_a_bare_level() {
+    let filter = Filter::parse("app=debug", LogLevel::Info).unwrap();
+    assert_eq!(filter.default_level(), LogLevel::Info);
+    assert_eq!(Filter::parse("", LogLevel::Error).unwrap().default_level(), LogLevel::Error);
+}
+
+#[test]
+fn rejects_unknown_levels() {
+    let err = Filter::parse("app=loud", LogLevel::Info).unwrap_err();
+    assert_eq!(err.to_string(), "invalid log directive: \"app=loud\"");
+    assert!(Filter::parse("verbose", LogLevel::Info).is_err());
+}
+
+#[test]
+fn targets_match_on_module_boundaries() {
+    let filter = Filter::new(LogLevel::Info).directive("app::db", LogLevel::Trace);
+    assert_eq!(filter.level_for("app::db"), LogLevel::Trace);
+    assert_eq!(filter.level_for("app::db::pool"), LogLevel::Trace);
+    assert_eq!(filter.level_for("app::dbx"), LogLevel::Info);
+    assert_eq!(filter.level_for("app"), LogLevel::Info);
+}
+
+#[test]
+fn the_longest_matching_target_wins() {
+    let filter = Filter::new(LogLevel::Info)
+        .directive("app::db::pool", LogLevel::Error)
+        .directive("app", LogLevel::Debug)
+        .directive("app::db", LogLevel::Trace);
+    assert_eq!(filter.level_for("app::db::pool::conn"), LogLevel::Error);
+    assert_eq!(filter.level_for("app::db::query"), LogLevel::Trace);
+    assert_eq!(filter.level_for("app::http"), LogLevel::Debug);
+    assert!(filter.enabled("app::db", LogLevel::Trace));
+    assert!(!filter.enabled("app::db::pool", LogLevel::Warning));
+}
+
+#[test]
+fn a_later_directive_replaces_the_same_target() {
+    let filter = Filter::parse("app=error,app=trace", LogLevel::Info).unwrap();
+    assert_eq!(filter.level_for("app"), LogLevel::Trace);
+}
+
+#[test]
+fn min_level_is_the_most_verbose_directive() {
+    let filter = Filter::parse("warn,app=debug,hyper=error", LogLevel::Info).unwrap();
+    assert_eq!(filter.min_level(), LogLevel::Debug);
+    assert_eq!(Filter::new(LogLevel::Error).min_level(), LogLevel::Error);
+}
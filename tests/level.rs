@@ -0,0 +1,21 @@
+use vapor::error::VaporError;
+use vapor::level::register_level;
+use vapor::logger::LogLevel;
+
+#[test]
+fn registers_levels_between_the_built_in_ones() {
+    let notice = register_level("notice", 250, (0, 128, 255), "notice").unwrap();
+    assert!(notice > LogLevel::Info && notice < LogLevel::Warning);
+    assert_eq!(notice.as_str(), "notice");
+    assert_eq!(LogLevel::from_name("NOTICE"), Some(notice));
+}
+
+#[test]
+fn rejects_duplicate_and_built_in_names() {
+    register_level("audit", 350, (255, 0, 255), "audit").unwrap();
+    for name in ["audit", "Audit", "info", "WARN", "warning"] {
+        let err = register_level(name, 10, (0, 0, 0), name).unwrap_err();
+        assert!(matches!(err, VaporError::DuplicateLevel(ref duplicate) if duplicate == name), "{}", name);
+    }
+    assert_eq!(LogLevel::from_name("audit").map(|level| level.severity()), Some(350));
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "log")]
+
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use vapor::filter::Filter;
+use vapor::log_bridge;
+use vapor::logger::{LOGGER, LogLevel, Record};
+use vapor::sink::Sink;
+
+#[derive(Default)]
+struct Capture(Mutex<Vec<String>>);
+
+impl Sink for Capture {
+    fn write(&self, record: &Record) {
+        self.0.lock().unwrap().push(record.message.clone());
+    }
+}
+
+struct Counted(AtomicUsize);
+
+impl fmt::Display for Counted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        f.write_str("counted")
+    }
+}
+
+#[test]
+fn follows_the_filter_and_skips_disabled_records() {
+    let sink = Arc::new(Capture::default());
+    {
+        let mut logger = LOGGER.lock().unwrap();
+        logger.clear_sinks();
+        logger.add_shared_sink(sink.clone());
+        logger.set_filter(Filter::new(LogLevel::Warning).directive("noisy", LogLevel::Debug));
+    }
+    log_bridge::install().unwrap();
+    assert_eq!(log::max_level(), log::LevelFilter::Debug);
+
+    let counted = Counted(AtomicUsize::new(0));
+    log::debug!("quiet {}", counted);
+    log::debug!(target: "noisy::part", "loud {}", counted);
+    log::warn!("kept");
+    assert_eq!(counted.0.load(Ordering::SeqCst), 1);
+    assert_eq!(*sink.0.lock().unwrap(), ["loud counted", "kept"]);
+
+    let mut logger = LOGGER.lock().unwrap();
+    logger.set_filter(Filter::new(LogLevel::Fatal));
+    log_bridge::update_max_level(&logger);
+    assert_eq!(log::max_level(), log::LevelFilter::Off);
+}
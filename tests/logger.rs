@@ -0,0 +1,34 @@
+use std::sync::{Arc, Mutex};
+use vapor::logger::{LogLevel, Logger, Record};
+use vapor::sink::Sink;
+use vapor::theme::Theme;
+
+#[derive(Default)]
+struct Capture(Mutex<Vec<Record>>);
+
+impl Sink for Capture {
+    fn write(&self, record: &Record) {
+        self.0.lock().unwrap().push(record.clone());
+    }
+}
+
+#[test]
+fn children_keep_their_settings_to_themselves() {
+    let sink = Arc::new(Capture::default());
+    let mut parent = Logger::new();
+    parent.clear_sinks();
+    parent.add_shared_sink(sink.clone());
+    let mut child = parent.child(&[("component", "db".into())]);
+    child.set_theme(Theme::by_name("monochrome").unwrap());
+    child.set_language("zh-cn");
+
+    parent.log(LogLevel::Info, "parent", "info", &[], &[]);
+    child.log(LogLevel::Info, "child", "info", &[], &[]);
+
+    let records = sink.0.lock().unwrap();
+    assert_eq!(*records[0].theme, Theme::by_name("vapor").unwrap());
+    assert_eq!((records[0].language.as_str(), records[0].fields.len()), ("en", 0));
+    assert_eq!(*records[1].theme, Theme::by_name("monochrome").unwrap());
+    assert_eq!(records[1].language, "zh-cn");
+    assert_eq!(records[1].fields, [("component".to_string(), "db".into())]);
+}
@@ -0,0 +1,101 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::{env, fmt, fs, process};
+use vapor::file::FileSink;
+use vapor::locale::MissingPolicy;
+use vapor::logger::{LOGGER, LogLevel, Logger, Record};
+use vapor::sink::{Format, Sink};
+use vapor::{debug, info};
+
+static SERIAL: Mutex<()> = Mutex::new(());
+
+#[derive(Default)]
+struct Capture(Mutex<Vec<Record>>);
+
+impl Sink for Capture {
+    fn write(&self, record: &Record) {
+        self.0.lock().unwrap().push(record.clone());
+    }
+}
+
+fn capture(min_level: LogLevel) -> Arc<Capture> {
+    let sink = Arc::new(Capture::default());
+    let mut logger = LOGGER.lock().unwrap();
+    logger.clear_sinks();
+    logger.add_shared_sink(sink.clone());
+    logger.set_language("en");
+    logger.set_min_level(min_level);
+    sink
+}
+
+/// Logs from its own `Display` impl and counts how often it was formatted.
+struct Chatty(AtomicUsize);
+
+impl fmt::Display for Chatty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        debug!("config.rejected", path = "inner", error = "nested");
+        f.write_str("chatty")
+    }
+}
+
+#[test]
+fn arguments_may_log_while_being_formatted() {
+    let _serial = SERIAL.lock().unwrap();
+    let sink = capture(LogLevel::Trace);
+    let chatty = Chatty(AtomicUsize::new(0));
+    info!("config.rejected", path = "outer", error = chatty; detail = chatty);
+
+    let records = sink.0.lock().unwrap();
+    let messages: Vec<&str> = records.iter().map(|record| record.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        [
+            "Rejected configuration inner, keeping the previous one: nested",
+            "Rejected configuration inner, keeping the previous one: nested",
+            "Rejected configuration outer, keeping the previous one: chatty"
+        ]
+    );
+}
+
+#[test]
+fn disabled_levels_do_not_format_arguments() {
+    let _serial = SERIAL.lock().unwrap();
+    let sink = capture(LogLevel::Info);
+    let chatty = Chatty(AtomicUsize::new(0));
+    debug!("config.rejected", path = "outer", error = chatty; detail = chatty);
+
+    assert_eq!(chatty.0.load(Ordering::SeqCst), 0);
+    assert!(sink.0.lock().unwrap().is_empty());
+}
+
+#[test]
+fn a_missing_key_panics_without_poisoning_the_logger() {
+    let _serial = SERIAL.lock().unwrap();
+    let sink = capture(LogLevel::Trace);
+    LOGGER.lock().unwrap().set_missing_policy(MissingPolicy::Panic);
+    let key = "no.such.key";
+    let result = panic::catch_unwind(AssertUnwindSafe(|| info!(key)));
+    LOGGER.lock().unwrap().set_missing_policy(MissingPolicy::default());
+
+    assert!(result.is_err());
+    assert!(!LOGGER.is_poisoned());
+    info!("config.rejected", path = "after", error = "panic");
+    assert_eq!(sink.0.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn json_keeps_field_types() {
+    let path = env::temp_dir().join(format!("vapor-json-{}.log", process::id()));
+    let _ = fs::remove_file(&path);
+    let mut logger = Logger::new();
+    logger.clear_sinks();
+    logger.add_sink(FileSink::new(&path).unwrap().format(Format::Json));
+    info!(logger: logger, "config.rejected", path = "a", error = "b"; bytes = 1024, ratio = -0.5, ok = true, id = "123", code = %7, name = String::from("vapor"));
+    logger.flush();
+
+    let line: serde_json::Value = serde_json::from_str(fs::read_to_string(&path).unwrap().trim()).unwrap();
+    assert_eq!(line["fields"], serde_json::json!({"bytes": 1024, "ratio": -0.5, "ok": true, "id": "123", "code": "7", "name": "vapor"}));
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,112 @@
+use serde_json::Value;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+use std::{env, process};
+use vapor::logger::{LOGGER, LogLevel};
+use vapor::reload::{self, ReloadGuard};
+
+const INTERVAL: Duration = Duration::from_millis(10);
+
+/// The watcher reconfigures the global logger, so tests run one at a time.
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn serial() -> MutexGuard<'static, ()> {
+    let guard = SERIAL.lock().unwrap_or_else(PoisonError::into_inner);
+    for name in ["VAPOR_LEVEL", "VAPOR_LANG", "VAPOR_TZ", "VAPOR_LOG"] {
+        unsafe { env::remove_var(name) };
+    }
+    guard
+}
+
+/// Writes a config that logs JSON to `app.log` next to it. The file is replaced in one step, so the
+/// watcher never sees it half written.
+fn config(dir: &Path, level: &str, language: &str, modified: Option<SystemTime>) {
+    let content = format!(
+        "level: {}\nlanguage: {}\nsinks:\n  - type: file\n    path: app.log\n    format: json\n",
+        level, language
+    );
+    let staged = dir.join("vapor.yml.new");
+    fs::write(&staged, content).unwrap();
+    if let Some(modified) = modified {
+        File::options().write(true).open(&staged).unwrap().set_modified(modified).unwrap();
+    }
+    fs::rename(staged, dir.join("vapor.yml")).unwrap();
+}
+
+fn start(test: &str) -> (PathBuf, ReloadGuard) {
+    let dir = env::temp_dir().join(format!("vapor-reload-{}-{}", test, process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    config(&dir, "info", "en", None);
+    let guard = reload::watch(dir.join("vapor.yml"), INTERVAL).unwrap();
+    (dir, guard)
+}
+
+/// The keys and languages written to `app.log` so far.
+fn logged(dir: &Path) -> Vec<(String, String)> {
+    LOGGER.lock().unwrap().flush();
+    let content = fs::read_to_string(dir.join("app.log")).unwrap_or_default();
+    content
+        .lines()
+        .map(|line| {
+            let line: Value = serde_json::from_str(line).unwrap();
+            (line["key"].as_str().unwrap().to_string(), line["lang"].as_str().unwrap().to_string())
+        })
+        .collect()
+}
+
+fn wait_for(dir: &Path, key: &str) -> Vec<(String, String)> {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    loop {
+        let logged = logged(dir);
+        if logged.iter().any(|(logged, _)| logged == key) {
+            return logged;
+        }
+        assert!(Instant::now() < deadline, "{} was not logged", key);
+        thread::sleep(INTERVAL);
+    }
+}
+
+#[test]
+fn applies_a_valid_edit() {
+    let _serial = serial();
+    let (dir, _guard) = start("valid");
+    assert!(!LOGGER.lock().unwrap().enabled("app", LogLevel::Debug));
+
+    config(&dir, "debug", "zh-cn", None);
+    let logged = wait_for(&dir, "config.reloaded");
+    assert_eq!(logged, [("config.reloaded".to_string(), "zh-cn".to_string())]);
+    let logger = LOGGER.lock().unwrap();
+    assert!(logger.enabled("app", LogLevel::Debug));
+    assert_eq!(logger.locales().next(), Some("zh-cn"));
+}
+
+#[test]
+fn rejects_an_invalid_edit_and_keeps_the_old_settings() {
+    let _serial = serial();
+    let (dir, _guard) = start("invalid");
+
+    config(&dir, "loud", "zh-cn", None);
+    let logged = wait_for(&dir, "config.rejected");
+    assert_eq!(logged, [("config.rejected".to_string(), "en".to_string())]);
+    let logger = LOGGER.lock().unwrap();
+    assert!(logger.enabled("app", LogLevel::Info) && !logger.enabled("app", LogLevel::Debug));
+    assert_eq!(logger.locales().next(), Some("en"));
+}
+
+#[test]
+fn ignores_a_file_whose_stamp_is_unchanged() {
+    let _serial = serial();
+    let (dir, _guard) = start("unchanged");
+    let modified = fs::metadata(dir.join("vapor.yml")).unwrap().modified().unwrap();
+
+    // Same length and modification time, so the watcher cannot tell the file changed.
+    config(&dir, "warn", "en", Some(modified));
+    thread::sleep(INTERVAL * 10);
+
+    assert!(logged(&dir).is_empty());
+    assert!(LOGGER.lock().unwrap().enabled("app", LogLevel::Info));
+}
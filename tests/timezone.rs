@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use vapor::logger::LOGGER;
+use vapor::tz;
+
+fn local_time(zone: &str, instant: &str) -> String {
+    let instant: DateTime<Utc> = instant.parse().unwrap();
+    let mut logger = LOGGER.lock().unwrap();
+    logger.set_timezone(tz!(zone));
+    logger.time_at(instant).to_rfc3339()
+}
+
+#[test]
+fn converts_the_real_instant_into_the_configured_zone() {
+    assert_eq!(local_time("Asia/Shanghai", "2024-06-01T00:00:00Z"), "2024-06-01T08:00:00+08:00");
+    assert_eq!(local_time("Asia/Kolkata", "2024-06-01T00:00:00Z"), "2024-06-01T05:30:00+05:30");
+    assert_eq!(local_time("Etc/UTC", "2024-06-01T00:00:00Z"), "2024-06-01T00:00:00+00:00");
+    assert_eq!(local_time("America/Los_Angeles", "2024-06-01T00:00:00Z"), "2024-05-31T17:00:00-07:00");
+}
+
+#[test]
+fn new_york_across_dst_transitions() {
+    // 2024-03-10 02:00 local does not exist.
+    assert_eq!(local_time("America/New_York", "2024-03-10T06:59:59Z"), "2024-03-10T01:59:59-05:00");
+    assert_eq!(local_time("America/New_York", "2024-03-10T07:00:00Z"), "2024-03-10T03:00:00-04:00");
+    // 2024-11-03 01:30 local happens twice.
+    assert_eq!(local_time("America/New_York", "2024-11-03T05:30:00Z"), "2024-11-03T01:30:00-04:00");
+    assert_eq!(local_time("America/New_York", "2024-11-03T06:30:00Z"), "2024-11-03T01:30:00-05:00");
+}
+
+#[test]
+fn london_across_dst_transitions() {
+    assert_eq!(local_time("Europe/London", "2024-03-31T00:59:59Z"), "2024-03-31T00:59:59+00:00");
+    assert_eq!(local_time("Europe/London", "2024-03-31T01:00:00Z"), "2024-03-31T02:00:00+01:00");
+    assert_eq!(local_time("Europe/London", "2024-10-27T00:30:00Z"), "2024-10-27T01:30:00+01:00");
+    assert_eq!(local_time("Europe/London", "2024-10-27T01:30:00Z"), "2024-10-27T01:30:00+00:00");
+}
+
+#[test]
+fn sydney_across_dst_transitions() {
+    assert_eq!(local_time("Australia/Sydney", "2024-04-06T15:30:00Z"), "2024-04-07T02:30:00+11:00");
+    assert_eq!(local_time("Australia/Sydney", "2024-04-06T16:30:00Z"), "2024-04-07T02:30:00+10:00");
+    assert_eq!(local_time("Australia/Sydney", "2024-10-05T15:59:59Z"), "2024-10-06T01:59:59+10:00");
+    assert_eq!(local_time("Australia/Sydney", "2024-10-05T16:00:00Z"), "2024-10-06T03:00:00+11:00");
+}
+
@@ -0,0 +1,151 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, mpsc};
+use std::thread;
+use std::time::Duration;
+use vapor::logger::{LogLevel, Logger, Record};
+use vapor::sink::Sink;
+use vapor::writer::{Overflow, WriterGuard};
+
+/// Runs `f` on another thread and fails the test if it does not finish in time.
+fn within<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> T {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || sender.send(f()).unwrap());
+    receiver.recv_timeout(Duration::from_secs(5)).expect("timed out")
+}
+
+fn logger(sink: Arc<dyn Sink>) -> Logger {
+    let mut logger = Logger::new();
+    logger.clear_sinks();
+    logger.add_shared_sink(sink);
+    logger
+}
+
+/// Blocks its first write until released, then panics. Later writes are recorded.
+struct Failing {
+    release: Mutex<mpsc::Receiver<()>>,
+    failed: AtomicBool,
+    written: Mutex<Vec<String>>,
+}
+
+impl Sink for Failing {
+    fn write(&self, record: &Record) {
+        if !self.failed.swap(true, Ordering::SeqCst) {
+            let _ = self.release.lock().unwrap().recv();
+            panic!("sink failed");
+        }
+        self.written.lock().unwrap().push(record.message.clone());
+    }
+}
+
+#[test]
+fn a_panicking_sink_releases_blocked_callers() {
+    let (release, receiver) = mpsc::channel();
+    let sink = Arc::new(Failing {
+        release: Mutex::new(receiver),
+        failed: AtomicBool::new(false),
+        written: Mutex::new(Vec::new()),
+    });
+    let mut logger = logger(sink.clone());
+    let guard = logger.spawn_writer(1, Overflow::Block);
+
+    logger.log_message(LogLevel::Info, "writer", "one", &[]);
+    logger.log_message(LogLevel::Info, "writer", "two", &[]);
+    let blocked = logger.clone();
+    let producer = thread::spawn(move || blocked.log_message(LogLevel::Info, "writer", "three", &[]));
+    thread::sleep(Duration::from_millis(50));
+    release.send(()).unwrap();
+
+    within(move || producer.join().unwrap());
+    let flushed = logger.clone();
+    within(move || flushed.flush());
+    assert_eq!(*sink.written.lock().unwrap(), ["three"]);
+    assert_eq!(guard.dropped(), 1);
+}
+
+/// Signals each write as it starts and holds it until a permit arrives or the permits are dropped.
+struct Gated {
+    entered: Mutex<mpsc::Sender<()>>,
+    permits: Mutex<mpsc::Receiver<()>>,
+    written: Mutex<Vec<String>>,
+}
+
+struct Gate {
+    entered: mpsc::Receiver<()>,
+    permits: mpsc::Sender<()>,
+    sink: Arc<Gated>,
+}
+
+fn gate() -> Gate {
+    let (entered, entered_receiver) = mpsc::channel();
+    let (permits, permits_receiver) = mpsc::channel();
+    Gate {
+        entered: entered_receiver,
+        permits,
+        sink: Arc::new(Gated {
+            entered: Mutex::new(entered),
+            permits: Mutex::new(permits_receiver),
+            written: Mutex::new(Vec::new()),
+        }),
+    }
+}
+
+impl Sink for Gated {
+    fn write(&self, record: &Record) {
+        let _ = self.entered.lock().unwrap().send(());
+        let _ = self.permits.lock().unwrap().recv();
+        self.written.lock().unwrap().push(record.message.clone());
+    }
+}
+
+/// Logs `one` and waits until the writer is stuck on it, then fills the queue with `two` and `three`.
+fn fill(overflow: Overflow) -> (Gate, Logger, WriterGuard) {
+    let gate = gate();
+    let mut logger = logger(gate.sink.clone());
+    let guard = logger.spawn_writer(2, overflow);
+    logger.log_message(LogLevel::Info, "writer", "one", &[]);
+    gate.entered.recv_timeout(Duration::from_secs(5)).unwrap();
+    logger.log_message(LogLevel::Info, "writer", "two", &[]);
+    logger.log_message(LogLevel::Info, "writer", "three", &[]);
+    (gate, logger, guard)
+}
+
+fn finish(gate: Gate, logger: Logger) -> Vec<String> {
+    drop(gate.permits);
+    within(move || logger.flush());
+    gate.sink.written.lock().unwrap().clone()
+}
+
+#[test]
+fn drop_newest_discards_the_incoming_record() {
+    let (gate, logger, guard) = fill(Overflow::DropNewest);
+    logger.log_message(LogLevel::Info, "writer", "four", &[]);
+    assert_eq!(guard.dropped(), 1);
+    assert_eq!(logger.dropped_records(), 1);
+    assert_eq!(finish(gate, logger), ["one", "two", "three"]);
+}
+
+#[test]
+fn drop_oldest_discards_the_oldest_queued_record() {
+    let (gate, logger, guard) = fill(Overflow::DropOldest);
+    logger.log_message(LogLevel::Info, "writer", "four", &[]);
+    logger.log_message(LogLevel::Info, "writer", "five", &[]);
+    assert_eq!(guard.dropped(), 2);
+    assert_eq!(finish(gate, logger), ["one", "four", "five"]);
+}
+
+#[test]
+fn block_waits_for_room_without_dropping() {
+    let (gate, logger, guard) = fill(Overflow::Block);
+    let (done, finished) = mpsc::channel();
+    let blocked = logger.clone();
+    thread::spawn(move || {
+        blocked.log_message(LogLevel::Info, "writer", "four", &[]);
+        done.send(()).unwrap();
+    });
+    assert!(finished.recv_timeout(Duration::from_millis(100)).is_err());
+
+    gate.permits.send(()).unwrap();
+    finished.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(guard.dropped(), 0);
+    assert_eq!(finish(gate, logger), ["one", "two", "three", "four"]);
+}